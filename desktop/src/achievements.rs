@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use game_core::GameState;
+use game_core::achievements::{ACHIEVEMENTS, Achievement, AchievementLog};
+use game_core::storage::Storage;
+
+/// Where the log is kept
+const KEY: &str = "achievements.json";
+
+/// How long an unlock toast stays up
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// The player's unlocked achievements, with a toast for each new one.
+pub struct Achievements {
+    log: AchievementLog,
+    storage: Arc<dyn Storage>,
+    /// Unlocks waiting to be announced, the one showing first
+    toasts: VecDeque<(&'static Achievement, Option<Instant>)>,
+}
+
+impl Achievements {
+    pub fn load(storage: Arc<dyn Storage>) -> Self {
+        let log = AchievementLog::load(&*storage, KEY).unwrap_or_else(|e| {
+            eprintln!("Could not read achievements: {}", e);
+            AchievementLog::default()
+        });
+        Self {
+            log,
+            storage,
+            toasts: VecDeque::new(),
+        }
+    }
+
+    /// Unlock what the game has earned, saving and announcing new ones
+    pub fn record(&mut self, state: &GameState, maze: &str) {
+        let unlocked = self.log.record(state, maze);
+        if unlocked.is_empty() {
+            return;
+        }
+        self.toasts
+            .extend(unlocked.into_iter().map(|achievement| (achievement, None)));
+        if let Err(e) = self.log.save(&*self.storage, KEY) {
+            eprintln!("Could not save achievements: {}", e);
+        }
+    }
+
+    /// Announce new unlocks one at a time in the corner
+    pub fn show_toast(&mut self, ctx: &egui::Context) {
+        let Some((achievement, shown_at)) = self.toasts.front_mut() else {
+            return;
+        };
+        let elapsed = shown_at.get_or_insert_with(Instant::now).elapsed();
+        if elapsed >= TOAST_DURATION {
+            self.toasts.pop_front();
+            ctx.request_repaint();
+            return;
+        }
+        let achievement: &Achievement = achievement;
+
+        egui::Area::new(egui::Id::new("achievement_toast"))
+            .anchor(egui::Align2::RIGHT_TOP, [-10.0, 40.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.strong(format!("🏆 {}", achievement.title));
+                    ui.label(achievement.description);
+                });
+            });
+        ctx.request_repaint_after(TOAST_DURATION - elapsed);
+    }
+
+    /// Every achievement, unlocked ones first marked with where
+    pub fn show_panel(&self, ui: &mut egui::Ui) {
+        let unlocked = ACHIEVEMENTS
+            .iter()
+            .filter(|a| self.log.is_unlocked(a.id))
+            .count();
+        ui.label(format!("{} of {} unlocked", unlocked, ACHIEVEMENTS.len()));
+        ui.separator();
+        for achievement in ACHIEVEMENTS {
+            match self.log.earned_in(achievement.id) {
+                Some(maze) => {
+                    ui.strong(format!("🏆 {}", achievement.title));
+                    ui.label(achievement.description);
+                    ui.weak(format!("Earned in {}", maze));
+                }
+                None => {
+                    ui.weak(format!("🔒 {}", achievement.title));
+                    ui.weak(achievement.description);
+                }
+            }
+            ui.add_space(6.0);
+        }
+    }
+}
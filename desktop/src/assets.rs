@@ -0,0 +1,27 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Finds a file the maze refers to, such as a font, image or sound.
+///
+/// Mazes name their files relative to the maze file and cannot reach
+/// outside its folder, so a downloaded maze cannot read other files.
+/// Fails if the path leaves the folder, the file is missing or it is
+/// larger than `max_bytes`.
+pub fn resolve(
+    maze_dir: &Path,
+    file: &str,
+    max_bytes: u64,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let relative = Path::new(file);
+    if file.is_empty()
+        || !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err("path must be relative to the maze file".into());
+    }
+    let path = maze_dir.join(relative);
+    if std::fs::metadata(&path)?.len() > max_bytes {
+        return Err("file is too large".into());
+    }
+    Ok(path)
+}
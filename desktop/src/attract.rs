@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use eframe::egui;
+use game_core::GameState;
+use game_core::rng::Rng;
+use game_core::simulate::{Policy, PreferUnvisited};
+
+/// Delay between moves of the demo player
+const STEP_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// Self-playing demo shown while the app is idle.
+/// Runs on its own copy of the maze so the player's session is untouched.
+pub struct AttractMode {
+    start: GameState,
+    state: GameState,
+    policy: PreferUnvisited,
+    rng: Rng,
+    last_step: Instant,
+}
+
+impl AttractMode {
+    pub fn new(start: GameState) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self {
+            state: start.clone(),
+            start,
+            policy: PreferUnvisited::default(),
+            rng: Rng::new(seed),
+            last_step: Instant::now(),
+        }
+    }
+
+    /// The demo's current game state, for rendering
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Advance the demo by one move when it is due.
+    pub fn tick(&mut self, ctx: &egui::Context) {
+        if self.last_step.elapsed() >= STEP_INTERVAL {
+            self.last_step = Instant::now();
+            let playing = !self.state.play.is_finished() && !self.state.play.is_game_over();
+            // The demo skips cutscenes and always fights rather than running
+            if playing && (self.state.skip_cutscene() || self.state.attack()) {
+                ctx.request_repaint_after(STEP_INTERVAL);
+                return;
+            }
+            match self.policy.choose(&self.state, &mut self.rng) {
+                Some(index) if playing => {
+                    self.state.choose_exit(index);
+                    // The demo doesn't show what scripts print or react
+                    // to what happens
+                    self.state.take_messages();
+                    self.state.take_events();
+                }
+                _ => {
+                    // Finished, lost or stuck: start the demo over
+                    self.state = self.start.clone();
+                    self.policy.reset();
+                }
+            }
+        }
+        ctx.request_repaint_after(STEP_INTERVAL.saturating_sub(self.last_step.elapsed()));
+    }
+}
+
+/// Whether the player did anything this frame
+pub fn had_input(ctx: &egui::Context) -> bool {
+    ctx.input(|i| {
+        i.events.iter().any(|event| {
+            matches!(
+                event,
+                egui::Event::Key { pressed: true, .. }
+                    | egui::Event::PointerButton { pressed: true, .. }
+                    | egui::Event::PointerMoved(_)
+                    | egui::Event::MouseWheel { .. }
+                    | egui::Event::Touch { .. }
+                    | egui::Event::Text(_)
+            )
+        })
+    })
+}
@@ -0,0 +1,240 @@
+#[cfg(feature = "audio")]
+use std::collections::BTreeMap;
+use std::path::Path;
+#[cfg(feature = "audio")]
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use game_core::GameState;
+use game_core::ambience::Mixer;
+use game_core::cutscene::Playback;
+use game_core::event::GameEvent;
+#[cfg(feature = "audio")]
+use game_core::music::Intensity;
+
+#[cfg(feature = "audio")]
+use crate::assets;
+
+/// Largest sound file a maze may ship
+#[cfg(feature = "audio")]
+const MAX_SOUND_BYTES: u64 = 32 * 1024 * 1024;
+
+/// How often levels are updated while layers fade
+const FADE_STEP: Duration = Duration::from_millis(50);
+
+/// The maze's sounds: effects for what happens, cutscene beats' sounds,
+/// looping ambience for the rooms around the player, and its music.
+///
+/// Core works out what should sound and how loudly; this fades the
+/// levels and, in builds with the `audio` feature, plays them on the
+/// default output device. Without the feature or a device it is silent.
+pub struct Audio {
+    ambience: Mixer,
+    music: Mixer,
+    /// The cutscene beat whose sound was played last
+    beat: Option<Playback>,
+    updated_at: Instant,
+    #[cfg(feature = "audio")]
+    output: Option<Output>,
+}
+
+/// The output device and what is playing on it.
+#[cfg(feature = "audio")]
+struct Output {
+    device: rodio::MixerDeviceSink,
+    /// Effects by file, or `None` for ones that failed to load
+    effects: BTreeMap<String, Option<Arc<[u8]>>>,
+    /// Looping ambience by tag, or `None` for ones that failed to load
+    ambience: BTreeMap<String, Option<rodio::Player>>,
+    /// Music layers by name, all started together to stay in step
+    music: Option<BTreeMap<&'static str, rodio::Player>>,
+}
+
+impl Audio {
+    pub fn new() -> Self {
+        Self {
+            ambience: Mixer::default(),
+            music: Mixer::default(),
+            beat: None,
+            updated_at: Instant::now(),
+            #[cfg(feature = "audio")]
+            output: Output::open()
+                .inspect_err(|e| eprintln!("Sound unavailable: {}", e))
+                .ok(),
+        }
+    }
+
+    /// Stop everything, as when another maze is loaded
+    pub fn stop(&mut self) {
+        self.ambience = Mixer::default();
+        self.music = Mixer::default();
+        self.beat = None;
+        #[cfg(feature = "audio")]
+        if let Some(output) = &mut self.output {
+            output.effects.clear();
+            output.ambience.clear();
+            output.music = None;
+        }
+    }
+
+    /// Play the maze's sound for each event
+    pub fn play_events(
+        &mut self,
+        events: &[GameEvent],
+        state: &GameState,
+        maze_dir: &Path,
+        volume: f32,
+    ) {
+        for sound in events
+            .iter()
+            .filter_map(|event| state.maze.sounds.for_event(event))
+        {
+            self.play(sound, maze_dir, volume);
+        }
+    }
+
+    /// Fade the ambience and music towards what the game calls for, and
+    /// play the sound of a cutscene beat that just came up
+    pub fn update(&mut self, ctx: &egui::Context, state: &GameState, maze_dir: &Path, volume: f32) {
+        let elapsed = self.updated_at.elapsed();
+        self.updated_at = Instant::now();
+
+        if state.play.cutscene != self.beat {
+            self.beat = state.play.cutscene.clone();
+            let sound = state
+                .cutscene()
+                .and_then(|(cutscene, i)| cutscene.beats[i].sound.as_ref());
+            if let Some(sound) = sound {
+                self.play(sound, maze_dir, volume);
+            }
+        }
+
+        let ambience = state.ambient_levels();
+        let music = state.music_levels();
+        self.ambience.update(&ambience, elapsed);
+        self.music.update(&music, elapsed);
+        if self.ambience.is_fading(&ambience) || self.music.is_fading(&music) {
+            ctx.request_repaint_after(FADE_STEP);
+        }
+
+        #[cfg(feature = "audio")]
+        if let Some(output) = &mut self.output {
+            output.set_ambience(state, maze_dir, self.ambience.levels(), volume);
+            output.set_music(state, maze_dir, self.music.levels(), volume);
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = (maze_dir, volume);
+    }
+
+    fn play(&mut self, sound: &str, maze_dir: &Path, volume: f32) {
+        #[cfg(feature = "audio")]
+        if let Some(output) = &mut self.output {
+            output.play(sound, maze_dir, volume);
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = (sound, maze_dir, volume);
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Output {
+    fn open() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut device = rodio::DeviceSinkBuilder::open_default_sink()?;
+        device.log_on_drop(false);
+        Ok(Self {
+            device,
+            effects: BTreeMap::new(),
+            ambience: BTreeMap::new(),
+            music: None,
+        })
+    }
+
+    /// Play an effect once, over whatever else is playing
+    fn play(&mut self, sound: &str, maze_dir: &Path, volume: f32) {
+        let bytes = self.effects.entry(sound.to_string()).or_insert_with(|| {
+            let path = assets::resolve(maze_dir, sound, MAX_SOUND_BYTES);
+            path.and_then(|path| Ok(std::fs::read(path)?.into()))
+                .inspect_err(|e| eprintln!("Error loading sound {}: {}", sound, e))
+                .ok()
+        });
+        let Some(bytes) = bytes else {
+            return;
+        };
+        match rodio::Decoder::try_from(std::io::Cursor::new(bytes.clone())) {
+            Ok(decoder) => self
+                .device
+                .mixer()
+                .add(rodio::Source::amplify(decoder, volume)),
+            Err(e) => eprintln!("Error playing sound {}: {}", sound, e),
+        }
+    }
+
+    /// Start the layers that are sounding and stop the ones that faded out
+    fn set_ambience(
+        &mut self,
+        state: &GameState,
+        maze_dir: &Path,
+        levels: &BTreeMap<String, f32>,
+        volume: f32,
+    ) {
+        self.ambience.retain(|tag, _| levels.contains_key(tag));
+        for (tag, level) in levels {
+            let mixer = self.device.mixer();
+            let player = self.ambience.entry(tag.clone()).or_insert_with(|| {
+                let layer = state.maze.ambience.get(tag)?;
+                start_loop(mixer, &layer.sound, maze_dir)
+            });
+            if let Some(player) = player {
+                player.set_volume(level * volume);
+            }
+        }
+    }
+
+    /// Start every music layer together the first time, then set each
+    /// one's volume, with layers not sounding kept running silently
+    fn set_music(
+        &mut self,
+        state: &GameState,
+        maze_dir: &Path,
+        levels: &BTreeMap<String, f32>,
+        volume: f32,
+    ) {
+        let Some(music) = &state.maze.music else {
+            return;
+        };
+        let mixer = self.device.mixer();
+        let players = self.music.get_or_insert_with(|| {
+            Intensity::ALL
+                .into_iter()
+                .filter_map(|layer| {
+                    let player = start_loop(mixer, music.track(layer)?, maze_dir)?;
+                    Some((layer.name(), player))
+                })
+                .collect()
+        });
+        for (name, player) in players {
+            let level = levels.get(*name).copied().unwrap_or(0.0);
+            player.set_volume(level * volume);
+        }
+    }
+}
+
+/// Start a sound looping silently, ready to be faded in
+#[cfg(feature = "audio")]
+fn start_loop(mixer: &rodio::mixer::Mixer, sound: &str, maze_dir: &Path) -> Option<rodio::Player> {
+    let opened = assets::resolve(maze_dir, sound, MAX_SOUND_BYTES)
+        .and_then(|path| Ok(rodio::Decoder::try_from(std::fs::File::open(path)?)?));
+    match opened {
+        Ok(decoder) => {
+            let player = rodio::Player::connect_new(mixer);
+            player.set_volume(0.0);
+            player.append(rodio::Source::repeat_infinite(decoder));
+            Some(player)
+        }
+        Err(e) => {
+            eprintln!("Error loading sound {}: {}", sound, e);
+            None
+        }
+    }
+}
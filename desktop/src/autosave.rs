@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use game_core::save::SaveGame;
+use game_core::storage::Storage;
+
+/// Where the game in progress is kept
+pub const KEY: &str = "autosave.json";
+
+/// Quiet time after the last move before the game is written
+const DEBOUNCE: Duration = Duration::from_millis(750);
+
+/// How long a save error stays on screen
+const NOTICE_DURATION: Duration = Duration::from_secs(5);
+
+/// Writes the game to storage on a background thread. Bursts of moves are
+/// collapsed into one write once play pauses, or when the autosave is
+/// dropped as the game closes.
+pub struct Autosave {
+    /// Taken on drop, so the thread stops waiting and writes what it has
+    saves: Option<Sender<SaveGame>>,
+    worker: Option<JoinHandle<()>>,
+    errors: Receiver<String>,
+    /// Last error and when it arrived
+    notice: Option<(String, Instant)>,
+}
+
+impl Autosave {
+    pub fn spawn(storage: Arc<dyn Storage>, ctx: &egui::Context) -> Self {
+        let (saves, pending) = mpsc::channel::<SaveGame>();
+        let (report, errors) = mpsc::channel();
+        let ctx = ctx.clone();
+
+        let worker = thread::spawn(move || {
+            // Wait for a save, then keep taking newer ones until moves stop
+            // or the autosave is dropped
+            while let Ok(mut save) = pending.recv() {
+                while let Ok(newer) = pending.recv_timeout(DEBOUNCE) {
+                    save = newer;
+                }
+                if let Err(e) = save.write(&*storage, KEY) {
+                    let _ = report.send(format!("Autosave failed: {}", e));
+                    ctx.request_repaint();
+                }
+            }
+        });
+
+        Self {
+            saves: Some(saves),
+            worker: Some(worker),
+            errors,
+            notice: None,
+        }
+    }
+
+    /// Queues the game to be written
+    pub fn save(&self, save: SaveGame) {
+        if let Some(saves) = &self.saves {
+            let _ = saves.send(save);
+        }
+    }
+
+    /// Shows the latest save error, if any, as a toast
+    pub fn show_errors(&mut self, ctx: &egui::Context) {
+        if let Some(error) = self.errors.try_iter().last() {
+            eprintln!("{}", error);
+            self.notice = Some((error, Instant::now()));
+        }
+
+        let Some((message, shown_at)) = &self.notice else {
+            return;
+        };
+        let elapsed = shown_at.elapsed();
+        if elapsed >= NOTICE_DURATION {
+            self.notice = None;
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("autosave_notice"))
+            .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.colored_label(ui.visuals().warn_fg_color, message.as_str())
+                });
+            });
+        ctx.request_repaint_after(NOTICE_DURATION - elapsed);
+    }
+}
+
+impl Drop for Autosave {
+    /// Writes the last queued game before the window closes, so the moves
+    /// made just before quitting are kept
+    fn drop(&mut self) {
+        self.saves = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+#[cfg(feature = "gamepad")]
+use std::time::Duration;
+
+use eframe::egui;
+
+/// How much larger text and controls get
+const SCALE: f32 = 1.6;
+
+/// How often to look for gamepad input between other events
+#[cfg(feature = "gamepad")]
+const GAMEPAD_POLL: Duration = Duration::from_millis(30);
+
+/// Controller-first profile for TVs and handhelds such as the Steam
+/// Deck: large text, one panel at a time, everything reachable by moving
+/// focus with the arrow keys or D-pad, and an on-screen keyboard where
+/// text is typed.
+///
+/// Chosen with --big-picture or from the View menu. It also comes on by
+/// itself when launched from Steam's Big Picture or Game Mode, and, in
+/// builds with the `gamepad` feature, as soon as a gamepad is used.
+pub struct BigPicture {
+    active: bool,
+    /// The style from before the profile came on, to go back to
+    plain_style: Option<Arc<egui::Style>>,
+    #[cfg(feature = "gamepad")]
+    gamepads: Option<gilrs::Gilrs>,
+}
+
+impl BigPicture {
+    pub fn new(forced: bool) -> Self {
+        Self {
+            active: forced || launched_from_steam_ui(),
+            plain_style: None,
+            #[cfg(feature = "gamepad")]
+            gamepads: gilrs::Gilrs::new()
+                .inspect_err(|e| eprintln!("Gamepads unavailable: {}", e))
+                .ok(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn toggle(&mut self) {
+        self.active = !self.active;
+    }
+
+    /// Turn controller input into the keys egui moves focus with, and
+    /// give focus somewhere when there is none
+    pub fn feed(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        #[cfg(feature = "gamepad")]
+        self.read_gamepads(ctx, raw_input);
+
+        if !self.active || ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        // With nothing focused the arrows do nothing, so the first one
+        // focuses the first control instead
+        use egui::Key::{ArrowDown, ArrowLeft, ArrowRight, ArrowUp};
+        for event in &mut raw_input.events {
+            if let egui::Event::Key { key, .. } = event
+                && matches!(key, ArrowUp | ArrowDown | ArrowLeft | ArrowRight)
+            {
+                *key = egui::Key::Tab;
+            }
+        }
+    }
+
+    /// Put the larger style on or take it off, to match the profile, and
+    /// let widgets know which is in use
+    pub fn update(&mut self, ctx: &egui::Context) {
+        match (self.active, &self.plain_style) {
+            (true, None) => {
+                self.plain_style = Some(ctx.style());
+                ctx.style_mut(enlarge);
+            }
+            (false, Some(_)) => {
+                if let Some(style) = self.plain_style.take() {
+                    ctx.set_style(style);
+                }
+            }
+            _ => {}
+        }
+        ctx.data_mut(|d| d.insert_temp(egui::Id::new("big_picture"), self.active));
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn read_gamepads(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        use gilrs::{Button, EventType};
+
+        let Some(gamepads) = &mut self.gamepads else {
+            return;
+        };
+        while let Some(event) = gamepads.next_event() {
+            let (button, pressed) = match event.event {
+                EventType::ButtonPressed(button, _) => (button, true),
+                EventType::ButtonReleased(button, _) => (button, false),
+                _ => continue,
+            };
+            let (key, shift) = match button {
+                Button::DPadUp => (egui::Key::ArrowUp, false),
+                Button::DPadDown => (egui::Key::ArrowDown, false),
+                Button::DPadLeft => (egui::Key::ArrowLeft, false),
+                Button::DPadRight => (egui::Key::ArrowRight, false),
+                Button::South => (egui::Key::Enter, false),
+                Button::East => (egui::Key::Escape, false),
+                // Undo, as Backspace does on a keyboard
+                Button::West => (egui::Key::Backspace, false),
+                Button::LeftTrigger => (egui::Key::Tab, true),
+                Button::RightTrigger => (egui::Key::Tab, false),
+                _ => continue,
+            };
+            self.active = true;
+            raw_input.events.push(egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: egui::Modifiers {
+                    shift,
+                    ..Default::default()
+                },
+            });
+        }
+        if gamepads.gamepads().next().is_some() {
+            ctx.request_repaint_after(GAMEPAD_POLL);
+        }
+    }
+}
+
+/// Whether the profile is in use, for widgets that change with it
+pub fn is_active(ui: &egui::Ui) -> bool {
+    ui.data(|d| d.get_temp(egui::Id::new("big_picture")).unwrap_or(false))
+}
+
+/// Steam sets these when it runs a game in Big Picture or on a Deck
+fn launched_from_steam_ui() -> bool {
+    std::env::var_os("SteamDeck").is_some_and(|value| value == "1")
+        || std::env::var_os("SteamGamepadUI").is_some()
+}
+
+/// Bigger text and controls, and a bold outline on the focused one so it
+/// can be seen from across the room
+fn enlarge(style: &mut egui::Style) {
+    for font in style.text_styles.values_mut() {
+        font.size *= SCALE;
+    }
+    let spacing = &mut style.spacing;
+    spacing.interact_size *= SCALE;
+    spacing.button_padding *= SCALE;
+    spacing.item_spacing *= SCALE;
+    let visuals = &mut style.visuals;
+    visuals.selection.stroke.width = 3.0;
+    visuals.widgets.hovered.bg_stroke = egui::Stroke::new(3.0, visuals.warn_fg_color);
+}
+
+/// Letters, digits and space as buttons, for typing with a controller.
+/// Returns true if `text` changed.
+pub fn keyboard(ui: &mut egui::Ui, text: &mut String) -> bool {
+    const ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+    let before = text.len();
+    let mut erased = false;
+    for row in ROWS {
+        ui.horizontal(|ui| {
+            for key in row.chars() {
+                if ui.button(key.to_string()).clicked() {
+                    text.push(key);
+                }
+            }
+        });
+    }
+    ui.horizontal(|ui| {
+        if ui.button("Space").clicked() {
+            text.push(' ');
+        }
+        if ui.button("⌫ Delete").clicked() {
+            erased = text.pop().is_some();
+        }
+    });
+    erased || text.len() != before
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use eframe::egui;
+use game_core::GameState;
+use game_core::collection::{Collection, Rarity};
+use game_core::storage::Storage;
+
+use crate::settings;
+
+/// Where the collection is kept
+const KEY: &str = "collection.json";
+
+/// The rooms the player has found across mazes, shown as cards.
+pub struct Cards {
+    collection: Collection,
+    storage: Arc<dyn Storage>,
+}
+
+impl Cards {
+    pub fn load(storage: Arc<dyn Storage>) -> Self {
+        let collection = Collection::load(&*storage, KEY).unwrap_or_else(|e| {
+            eprintln!("Could not read collection: {}", e);
+            Collection::default()
+        });
+        Self {
+            collection,
+            storage,
+        }
+    }
+
+    /// Count a visit to the room the player is in, and save
+    pub fn visit(&mut self, state: &GameState, maze: &str) {
+        self.collection.visit(state, maze);
+        if let Err(e) = self.collection.save(&*self.storage, KEY) {
+            eprintln!("Could not save collection: {}", e);
+        }
+    }
+
+    /// A card for each room found, grouped by maze
+    pub fn show_panel(&self, ui: &mut egui::Ui) {
+        if self.collection.is_empty() {
+            ui.weak("Rooms you find are collected here.");
+            return;
+        }
+        let locale = settings::locale(ui);
+        ui.label(format!(
+            "{} rooms found",
+            locale.number(self.collection.len() as i64)
+        ));
+        for (maze, cards) in self.collection.mazes() {
+            ui.separator();
+            ui.strong(maze);
+            for (room, card) in cards {
+                let rarity = self.collection.rarity(maze, room).unwrap_or(Rarity::Common);
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal(|ui| {
+                        ui.strong(room);
+                        ui.colored_label(rarity_color(rarity), rarity.name());
+                    });
+                    ui.label(&card.excerpt);
+                    let times = if card.visits == 1 { "time" } else { "times" };
+                    ui.weak(format!("Visited {} {}", locale.number(card.visits), times));
+                });
+            }
+        }
+    }
+}
+
+fn rarity_color(rarity: Rarity) -> egui::Color32 {
+    match rarity {
+        Rarity::Common => egui::Color32::GRAY,
+        Rarity::Uncommon => egui::Color32::LIGHT_BLUE,
+        Rarity::Rare => egui::Color32::GOLD,
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+use eframe::egui;
+use game_core::GameState;
+use game_core::action::Action;
+use game_core::cutscene::Playback;
+use game_core::view::CutsceneView;
+
+use crate::{assets, widgets};
+
+/// Largest image file a cutscene may show
+const MAX_IMAGE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Widest a cutscene image is drawn
+const MAX_IMAGE_WIDTH: f32 = 480.0;
+
+/// Keeps time for the cutscene playing and loads its images.
+///
+/// Core only knows which beat is showing, so this starts a clock on each
+/// beat and asks for the next when the beat's delay is up.
+pub struct CutscenePlayer {
+    /// The beat being timed and when it came up
+    current: Option<(Playback, Instant)>,
+    /// Images by path, or `None` for ones that failed to load
+    images: HashMap<String, Option<egui::TextureHandle>>,
+}
+
+impl CutscenePlayer {
+    pub fn new() -> Self {
+        Self {
+            current: None,
+            images: HashMap::new(),
+        }
+    }
+
+    /// Time the current beat and load its image.
+    /// Returns the action to move on, when the beat is over or skipped.
+    pub fn update(
+        &mut self,
+        ctx: &egui::Context,
+        state: &GameState,
+        maze_dir: &Path,
+    ) -> Option<Action> {
+        let Some(playback) = &state.play.cutscene else {
+            self.current = None;
+            return None;
+        };
+        if self
+            .current
+            .as_ref()
+            .is_none_or(|(timed, _)| timed != playback)
+        {
+            self.current = Some((playback.clone(), Instant::now()));
+        }
+        let (cutscene, index) = state.cutscene()?;
+        let beat = &cutscene.beats[index];
+
+        if let Some(image) = &beat.image
+            && !self.images.contains_key(image)
+        {
+            let texture = load_image(ctx, image, maze_dir)
+                .inspect_err(|e| eprintln!("Error loading cutscene image {}: {}", image, e))
+                .ok();
+            self.images.insert(image.clone(), texture);
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            return Some(Action::SkipCutscene);
+        }
+        let delay = beat.delay()?;
+        let (_, started) = self.current.as_ref()?;
+        let elapsed = started.elapsed();
+        if elapsed >= delay {
+            return Some(Action::NextBeat);
+        }
+        ctx.request_repaint_after(delay - elapsed);
+        None
+    }
+
+    /// The text so far, the current beat's image, and buttons to go on
+    /// or skip, outlined at the scanning `highlight`.
+    /// Returns the action of the button clicked, if any.
+    pub fn show(
+        &self,
+        ui: &mut egui::Ui,
+        cutscene: &CutsceneView,
+        highlight: Option<usize>,
+    ) -> Option<Action> {
+        for beat in cutscene.shown {
+            ui.label(&beat.text);
+        }
+        let image = cutscene.beat.image.as_ref();
+        if let Some(Some(texture)) = image.and_then(|image| self.images.get(image)) {
+            ui.add_space(6.0);
+            ui.add(egui::Image::new(texture).max_width(MAX_IMAGE_WIDTH));
+        }
+        ui.add_space(10.0);
+
+        let mut clicked = None;
+        ui.horizontal(|ui| {
+            for (i, choice) in cutscene.choices.iter().enumerate() {
+                let button = widgets::choice_button(ui, &choice.label, highlight == Some(i));
+                if ui.add(button).clicked() {
+                    clicked = Some(choice.action.clone());
+                }
+            }
+        });
+        clicked
+    }
+}
+
+/// Reads a PNG from the maze's folder into a texture
+fn load_image(
+    ctx: &egui::Context,
+    image: &str,
+    maze_dir: &Path,
+) -> Result<egui::TextureHandle, Box<dyn std::error::Error>> {
+    let path = assets::resolve(maze_dir, image, MAX_IMAGE_BYTES)?;
+    let decoded = image::open(&path)?.into_rgba8();
+    let size = [decoded.width() as usize, decoded.height() as usize];
+    let pixels = egui::ColorImage::from_rgba_unmultiplied(size, decoded.as_raw());
+    Ok(ctx.load_texture(image, pixels, egui::TextureOptions::LINEAR))
+}
@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+
+use game_core::generate;
+
+/// URL scheme the game registers for links to mazes
+const SCHEME: &str = "maze://";
+
+/// What the game was asked to open at launch.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LaunchTarget {
+    /// A maze file, from a file association or the command line
+    File(PathBuf),
+    /// A generated maze, from a `maze://seed/<seed>[/<width>x<height>]` link
+    Random {
+        seed: u64,
+        width: usize,
+        height: usize,
+    },
+}
+
+/// Side of a linked random maze when the link gives no size
+const DEFAULT_SIZE: usize = 8;
+
+impl LaunchTarget {
+    /// Reads a command-line argument: a file path, a `file://` URL as some
+    /// desktops pass them, or a `maze://` link
+    pub fn parse(arg: &str) -> Result<Self, String> {
+        if let Some(path) = arg.strip_prefix("file://") {
+            return Ok(Self::File(PathBuf::from(percent_decode(path))));
+        }
+        let Some(link) = arg.strip_prefix(SCHEME) else {
+            return Ok(Self::File(PathBuf::from(arg)));
+        };
+
+        let parts: Vec<&str> = link.trim_end_matches('/').split('/').collect();
+        match parts.as_slice() {
+            ["seed", seed, size @ ..] => {
+                let seed = seed
+                    .parse()
+                    .map_err(|_| format!("{:?} is not a maze seed", seed))?;
+                let (width, height) = match size {
+                    [] => (DEFAULT_SIZE, DEFAULT_SIZE),
+                    [size] => parse_size(size)?,
+                    _ => return Err(format!("unexpected path in {}", arg)),
+                };
+                Ok(Self::Random {
+                    seed,
+                    width,
+                    height,
+                })
+            }
+            _ => Err(format!("unknown maze link {}", arg)),
+        }
+    }
+
+    /// The link that opens this random maze again
+    pub fn link(seed: u64, width: usize, height: usize) -> String {
+        format!("{}seed/{}/{}x{}", SCHEME, seed, width, height)
+    }
+}
+
+/// Reads `<width>x<height>`, or a single number for a square
+fn parse_size(size: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("{:?} is not a maze size", size);
+    let (width, height) = size.split_once('x').unwrap_or((size, size));
+    let width: usize = width.parse().map_err(|_| invalid())?;
+    let height: usize = height.parse().map_err(|_| invalid())?;
+    if !generate::SIZE_RANGE.contains(&width) || !generate::SIZE_RANGE.contains(&height) {
+        return Err(format!(
+            "maze sides must be {} to {} rooms",
+            generate::SIZE_RANGE.start(),
+            generate::SIZE_RANGE.end()
+        ));
+    }
+    Ok((width, height))
+}
+
+/// Undoes `%XX` escapes. Malformed escapes are kept as they are.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
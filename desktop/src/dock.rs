@@ -0,0 +1,48 @@
+use eframe::egui;
+use egui_dock::{NodeIndex, SurfaceIndex, TabViewer};
+
+use crate::layout::Panel;
+use crate::{GameAction, MazeApp};
+
+/// Draws the docked panels of the play screen and collects what the
+/// player did in them.
+pub struct Tabs<'a> {
+    pub app: &'a MazeApp,
+    pub action: Option<GameAction>,
+}
+
+impl TabViewer for Tabs<'_> {
+    type Tab = Panel;
+
+    fn title(&mut self, tab: &mut Panel) -> egui::WidgetText {
+        tab.title().into()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Panel) {
+        if let Some(action) = self.app.render_panel(ui, *tab) {
+            self.action = Some(action);
+        }
+    }
+
+    fn context_menu(
+        &mut self,
+        ui: &mut egui::Ui,
+        tab: &mut Panel,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+    ) {
+        if tab.can_detach() && ui.button("🗗 Open in its own window").clicked() {
+            self.action = Some(GameAction::Detach(*tab, true));
+            ui.close();
+        }
+    }
+
+    fn is_closeable(&self, _tab: &Panel) -> bool {
+        false
+    }
+
+    // Pop-outs are real windows instead, see Layout::show
+    fn allowed_in_windows(&self, _tab: &mut Panel) -> bool {
+        false
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+/// Most flashes a second allowed, whatever the settings say, following
+/// the three-flash guideline for photosensitive epilepsy
+pub const FLASH_RATE_CEILING: u32 = 3;
+
+/// How long the screen stays tinted after the player is hurt
+const FLASH_DURATION: Duration = Duration::from_millis(300);
+
+/// Strongest tint of a flash, kept well short of a full red screen
+const FLASH_ALPHA: f32 = 0.25;
+
+/// Every animation, fade and flash in the app goes through here, so the
+/// player's safety settings apply to all of them.
+///
+/// These settings come from the player alone. Mazes pick fonts and text
+/// sizes but have no way to reach this, so no maze can turn motion back
+/// on or flash faster than allowed.
+pub struct Effects {
+    reduced_motion: bool,
+    /// Shortest gap between two flashes
+    flash_gap: Duration,
+    /// When the last flash started
+    flashed_at: Option<Instant>,
+}
+
+impl Effects {
+    /// `max_flash_rate` is capped at [`FLASH_RATE_CEILING`]; zero turns
+    /// flashes off
+    pub fn new(reduced_motion: bool, max_flash_rate: u32) -> Self {
+        let rate = max_flash_rate.min(FLASH_RATE_CEILING);
+        Self {
+            reduced_motion,
+            flash_gap: match rate {
+                0 => Duration::MAX,
+                rate => Duration::from_secs(1) / rate,
+            },
+            flashed_at: None,
+        }
+    }
+
+    /// Tint the screen red for a moment, as when the player is hurt.
+    /// Dropped if it would come too soon after the last one, or at all
+    /// with reduced motion.
+    pub fn flash(&mut self) {
+        if self.reduced_motion || self.flash_gap == Duration::MAX {
+            return;
+        }
+        if self
+            .flashed_at
+            .is_some_and(|at| at.elapsed() < self.flash_gap)
+        {
+            return;
+        }
+        self.flashed_at = Some(Instant::now());
+    }
+
+    /// Keep egui's own animations in line with the settings, let widgets
+    /// know them, and paint any flash going on
+    pub fn update(&mut self, ctx: &egui::Context) {
+        if self.reduced_motion && ctx.style().animation_time > 0.0 {
+            ctx.style_mut(|style| {
+                style.animation_time = 0.0;
+                style.scroll_animation = egui::style::ScrollAnimation::none();
+            });
+        }
+        ctx.data_mut(|d| d.insert_temp(egui::Id::new("reduced_motion"), self.reduced_motion));
+
+        let Some(elapsed) = self.flashed_at.map(|at| at.elapsed()) else {
+            return;
+        };
+        if elapsed >= FLASH_DURATION {
+            return;
+        }
+        let fade = 1.0 - elapsed.as_secs_f32() / FLASH_DURATION.as_secs_f32();
+        let tint = egui::Color32::RED.gamma_multiply(FLASH_ALPHA * fade);
+        let layer = egui::LayerId::new(egui::Order::Foreground, egui::Id::new("flash"));
+        ctx.layer_painter(layer)
+            .rect_filled(ctx.content_rect(), 0.0, tint);
+        ctx.request_repaint();
+    }
+}
+
+/// Seconds an animation of `seconds` should take: none with reduced
+/// motion
+pub fn duration(ui: &egui::Ui, seconds: f32) -> f32 {
+    let reduced = ui.data(|d| d.get_temp(egui::Id::new("reduced_motion")).unwrap_or(false));
+    if reduced { 0.0 } else { seconds }
+}
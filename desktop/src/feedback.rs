@@ -0,0 +1,48 @@
+use eframe::egui;
+
+use crate::big_picture;
+
+/// Star rating and comment box for the results screen.
+/// Returns the stars and comment once the player submits.
+pub fn rating_prompt(ui: &mut egui::Ui) -> Option<(u8, String)> {
+    // Keep the unsent rating in egui's memory so rendering stays read-only
+    let id = egui::Id::new("rating_prompt");
+    let (mut stars, mut comment): (u8, String) =
+        ui.data_mut(|d| d.get_temp(id).unwrap_or_default());
+    let mut submitted = None;
+
+    ui.group(|ui| {
+        ui.label("How did you like this maze?");
+        ui.horizontal(|ui| {
+            for n in 1..=5 {
+                let star = if n <= stars { "★" } else { "☆" };
+                if ui.selectable_label(n == stars, star).clicked() {
+                    stars = n;
+                }
+            }
+        });
+        let on_screen = big_picture::is_active(ui);
+        ui.add(
+            egui::TextEdit::multiline(&mut comment)
+                .hint_text("Comments (optional)")
+                .desired_rows(2)
+                .interactive(!on_screen),
+        );
+        if on_screen {
+            big_picture::keyboard(ui, &mut comment);
+        }
+        if ui
+            .add_enabled(stars > 0, egui::Button::new("Send rating"))
+            .clicked()
+        {
+            submitted = Some((stars, comment.clone()));
+        }
+    });
+
+    if submitted.is_some() {
+        ui.data_mut(|d| d.remove::<(u8, String)>(id));
+    } else {
+        ui.data_mut(|d| d.insert_temp(id, (stars, comment)));
+    }
+    submitted
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use eframe::egui;
+use game_core::TextStyle;
+
+use crate::assets;
+
+/// Largest font file a maze may ship
+const MAX_FONT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Font family name registered for the maze's own font
+const MAZE_FAMILY: &str = "maze";
+
+const DEFAULT_SIZE: f32 = 14.0;
+
+/// Install the maze's description font, if it asks for one.
+/// Any problem with the font file falls back to the default font.
+pub fn description_font(
+    ctx: &egui::Context,
+    style: Option<&TextStyle>,
+    maze_dir: &Path,
+) -> Option<egui::FontId> {
+    let style = style?;
+    let size = style.size.unwrap_or(DEFAULT_SIZE).clamp(8.0, 48.0);
+
+    let family = match &style.font {
+        Some(font) => match install_font(ctx, font, maze_dir) {
+            Ok(family) => family,
+            Err(e) => {
+                eprintln!(
+                    "Error loading maze font {}: {}. Using default font.",
+                    font, e
+                );
+                egui::FontFamily::Proportional
+            }
+        },
+        None => egui::FontFamily::Proportional,
+    };
+
+    Some(egui::FontId::new(size, family))
+}
+
+fn install_font(
+    ctx: &egui::Context,
+    font: &str,
+    maze_dir: &Path,
+) -> Result<egui::FontFamily, Box<dyn std::error::Error>> {
+    let path = assets::resolve(maze_dir, font, MAX_FONT_BYTES)?;
+    let bytes = std::fs::read(&path)?;
+
+    // egui panics on fonts it cannot parse, so check first
+    ab_glyph::FontVec::try_from_vec(bytes.clone())?;
+
+    let mut fonts = egui::FontDefinitions::default();
+    fonts.font_data.insert(
+        MAZE_FAMILY.to_owned(),
+        egui::FontData::from_owned(bytes).into(),
+    );
+
+    // Fall back to the default fonts for glyphs the maze font lacks
+    let mut chain = vec![MAZE_FAMILY.to_owned()];
+    chain.extend(
+        fonts.families[&egui::FontFamily::Proportional]
+            .iter()
+            .cloned(),
+    );
+    let family = egui::FontFamily::Name(MAZE_FAMILY.into());
+    fonts.families.insert(family.clone(), chain);
+
+    ctx.set_fonts(fonts);
+    Ok(family)
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use game_core::ghost::{self, Ghost};
+use game_core::storage::Storage;
+
+use crate::settings;
+
+/// Where the best runs are kept
+const KEY: &str = "ghosts.json";
+
+/// Racing against the player's best finish of the current maze, while
+/// recording this attempt in case it beats it.
+pub struct GhostRace {
+    storage: Arc<dyn Storage>,
+    maze: String,
+    best: Option<Ghost>,
+    run: Ghost,
+    started: Instant,
+    /// Whether this attempt reached an end and was saved
+    done: bool,
+}
+
+impl GhostRace {
+    pub fn new(storage: Arc<dyn Storage>, maze: &str, start_room: &str) -> Self {
+        let best = ghost::load_ghosts(&*storage, KEY)
+            .ok()
+            .and_then(|mut ghosts| ghosts.remove(maze));
+        Self {
+            storage,
+            maze: maze.to_string(),
+            best,
+            run: Ghost::new(start_room),
+            started: Instant::now(),
+            done: false,
+        }
+    }
+
+    /// Notes the player's current room, if it changed
+    pub fn entered(&mut self, room: &str) {
+        let moved = self
+            .run
+            .entries
+            .last()
+            .is_none_or(|entry| entry.room != room);
+        if moved && !self.done {
+            self.run.record(room, self.started.elapsed());
+        }
+    }
+
+    /// Ends the attempt, keeping it if it is a new best
+    pub fn finish(&mut self) {
+        if self.done {
+            return;
+        }
+        self.done = true;
+        match ghost::save_best(&*self.storage, KEY, &self.maze, &self.run) {
+            Ok(true) => eprintln!("New best time: {:.1} s", self.run.duration().as_secs_f32()),
+            Ok(false) => {}
+            Err(e) => eprintln!("Could not save ghost run: {}", e),
+        }
+    }
+
+    /// Where the ghost is and how the player compares to it
+    pub fn show(&self, ui: &mut egui::Ui) {
+        let Some(best) = &self.best else {
+            return;
+        };
+        let elapsed = if self.done {
+            self.run.duration()
+        } else {
+            self.started.elapsed()
+        };
+
+        let ghost_at = if elapsed >= best.duration() {
+            "at the end".to_string()
+        } else {
+            format!("in {}", best.room_at(elapsed).unwrap_or_default())
+        };
+        let mut text = format!("👻 Your best run is {}", ghost_at);
+
+        // Compare the times both reached the player's current room
+        let locale = settings::locale(ui);
+        if let Some(entry) = self.run.entries.last()
+            && let Some(ghost_time) = best.reached(&entry.room)
+        {
+            let player_time = Duration::from_millis(entry.at_ms);
+            if player_time <= ghost_time {
+                let lead = ghost_time - player_time;
+                let lead = locale.decimal(lead.as_secs_f64(), 1);
+                text.push_str(&format!(" · you are {} s ahead", lead));
+            } else {
+                let lag = player_time - ghost_time;
+                let lag = locale.decimal(lag.as_secs_f64(), 1);
+                text.push_str(&format!(" · you are {} s behind", lag));
+            }
+        }
+        ui.weak(text);
+
+        // Keep the ghost moving between the player's moves
+        if !self.done && elapsed < best.duration() {
+            ui.ctx().request_repaint_after(Duration::from_millis(250));
+        }
+    }
+}
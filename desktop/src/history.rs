@@ -0,0 +1,68 @@
+use std::collections::VecDeque;
+
+use eframe::egui;
+
+/// Height of the scrollback log above the current description
+const LOG_HEIGHT: f32 = 160.0;
+
+/// Interactive-fiction style scrollback: the descriptions of the last few
+/// rooms, kept above the current one so the player can reread them.
+pub struct Scrollback {
+    limit: usize,
+    entries: VecDeque<String>,
+}
+
+impl Scrollback {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            entries: VecDeque::with_capacity(limit),
+        }
+    }
+
+    /// Adds the description of a room the player just left
+    pub fn record(&mut self, description: &str) {
+        if self.entries.len() == self.limit {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(description.to_owned());
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Shows the log, oldest first, scrolled to the most recent entry
+    pub fn show(&self, ui: &mut egui::Ui, font: Option<&egui::FontId>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        self.show_entries(ui, font, LOG_HEIGHT);
+        ui.separator();
+    }
+
+    /// Shows the whole log in a window of its own
+    pub fn show_window(&self, ui: &mut egui::Ui, font: Option<&egui::FontId>) {
+        if self.entries.is_empty() {
+            ui.weak("Rooms you leave are written down here.");
+        }
+        self.show_entries(ui, font, ui.available_height());
+    }
+
+    fn show_entries(&self, ui: &mut egui::Ui, font: Option<&egui::FontId>, max_height: f32) {
+        egui::ScrollArea::vertical()
+            .id_salt("scrollback")
+            .max_height(max_height)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for entry in &self.entries {
+                    let mut text = egui::RichText::new(entry).weak();
+                    if let Some(font) = font {
+                        text = text.font(font.clone());
+                    }
+                    ui.label(text);
+                    ui.add_space(6.0);
+                }
+            });
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use eframe::egui;
+use egui_dock::{DockState, NodeIndex};
+use game_core::storage::Storage;
+use serde::{Deserialize, Serialize};
+
+/// Where the layout is kept
+const KEY: &str = "layout.json";
+
+/// Size of a panel's window the first time it pops out
+const DEFAULT_WINDOW_SIZE: [f32; 2] = [320.0, 420.0];
+
+/// A panel of the play screen, shown as a tab that can be rearranged or
+/// popped out of the main window into its own.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Panel {
+    /// The room description and its exits
+    Play,
+    /// Inventory and bookmarks
+    Inventory,
+    /// Descriptions of the rooms passed through
+    Journal,
+    /// Progress through the maze
+    Stats,
+    /// Achievements unlocked and still to earn
+    Achievements,
+    /// Rooms found across every maze played
+    Collection,
+    /// The visited rooms and how they join up
+    Map,
+}
+
+impl Panel {
+    pub const ALL: [Panel; 7] = [
+        Self::Play,
+        Self::Inventory,
+        Self::Journal,
+        Self::Stats,
+        Self::Achievements,
+        Self::Collection,
+        Self::Map,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Play => "Play",
+            Self::Inventory => "Inventory",
+            Self::Journal => "Journal",
+            Self::Stats => "Stats",
+            Self::Achievements => "Achievements",
+            Self::Collection => "Collection",
+            Self::Map => "Map",
+        }
+    }
+
+    /// Whether the panel may leave the main window. The game itself stays.
+    pub fn can_detach(self) -> bool {
+        self != Self::Play
+    }
+
+    fn viewport_id(self) -> egui::ViewportId {
+        egui::ViewportId::from_hash_of(("panel", self))
+    }
+}
+
+/// Where a popped-out panel's window was, in screen points
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct WindowPlace {
+    pub position: Option<[f32; 2]>,
+    pub size: [f32; 2],
+}
+
+/// How the player arranged the panels: the tabs docked in the main
+/// window, and which panels are in their own windows and where. Kept in
+/// storage so the arrangement survives restarts.
+#[derive(Serialize, Deserialize)]
+pub struct Layout {
+    #[serde(default = "default_dock")]
+    pub dock: DockState<Panel>,
+    #[serde(default)]
+    windows: BTreeMap<Panel, WindowPlace>,
+    /// Where each window was put when it opened. Windows are created
+    /// there and then left to the player; changing it every frame would
+    /// fight their dragging.
+    #[serde(skip)]
+    opened: BTreeMap<Panel, WindowPlace>,
+    #[serde(skip)]
+    storage: Option<Arc<dyn Storage>>,
+}
+
+/// The game in the middle, the map, inventory, stats, achievements and
+/// the collection to the right, and the journal below them
+fn default_dock() -> DockState<Panel> {
+    let mut dock = DockState::new(vec![Panel::Play]);
+    let tree = dock.main_surface_mut();
+    let side = vec![
+        Panel::Map,
+        Panel::Inventory,
+        Panel::Stats,
+        Panel::Achievements,
+        Panel::Collection,
+    ];
+    let [_, right] = tree.split_right(NodeIndex::root(), 0.7, side);
+    tree.split_below(right, 0.55, vec![Panel::Journal]);
+    dock
+}
+
+impl Layout {
+    /// Reads the saved layout, or starts with the default one
+    pub fn load(storage: Arc<dyn Storage>) -> Self {
+        let mut layout: Self = storage
+            .read(KEY)
+            .ok()
+            .flatten()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_else(|| Self {
+                dock: default_dock(),
+                windows: BTreeMap::new(),
+                opened: BTreeMap::new(),
+                storage: None,
+            });
+
+        // Every panel is either docked or in a window, exactly once
+        layout.windows.retain(|panel, _| panel.can_detach());
+        for panel in Panel::ALL {
+            let docked = layout.dock.find_tab(&panel);
+            match (layout.windows.contains_key(&panel), docked) {
+                (true, Some(tab)) => {
+                    layout.dock.remove_tab(tab);
+                }
+                (false, None) => layout.dock.push_to_first_leaf(panel),
+                _ => {}
+            }
+        }
+        layout.opened = layout.windows.clone();
+        layout.storage = Some(storage);
+        layout
+    }
+
+    /// Panels in their own windows
+    pub fn detached(&self) -> impl Iterator<Item = Panel> + '_ {
+        self.windows.keys().copied()
+    }
+
+    /// Moves a panel into its own window, or back into the main one
+    pub fn set_detached(&mut self, panel: Panel, detached: bool) {
+        if detached && panel.can_detach() && !self.windows.contains_key(&panel) {
+            if let Some(tab) = self.dock.find_tab(&panel) {
+                self.dock.remove_tab(tab);
+            }
+            let place = WindowPlace {
+                position: None,
+                size: DEFAULT_WINDOW_SIZE,
+            };
+            self.windows.insert(panel, place);
+            self.opened.insert(panel, place);
+        } else if !detached && self.windows.remove(&panel).is_some() {
+            self.opened.remove(&panel);
+            self.dock.push_to_first_leaf(panel);
+        }
+        self.save();
+    }
+
+    /// Shows a detached panel in its window, or in a floating window
+    /// where the platform has only one. Returns what `contents` did and
+    /// whether the player closed the window.
+    pub fn show<R>(
+        &self,
+        ctx: &egui::Context,
+        panel: Panel,
+        contents: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> (Option<R>, bool) {
+        let place = self.opened.get(&panel).copied().unwrap_or(WindowPlace {
+            position: None,
+            size: DEFAULT_WINDOW_SIZE,
+        });
+        let mut builder = egui::ViewportBuilder::default()
+            .with_title(format!("Maze Game — {}", panel.title()))
+            .with_inner_size(place.size);
+        if let Some(position) = place.position {
+            builder = builder.with_position(position);
+        }
+
+        // The viewport callback is FnMut but runs once per frame
+        let mut contents = Some(contents);
+        ctx.show_viewport_immediate(panel.viewport_id(), builder, |ctx, class| {
+            let Some(contents) = contents.take() else {
+                return (None, false);
+            };
+            if class == egui::ViewportClass::Embedded {
+                let mut open = true;
+                let result = egui::Window::new(panel.title())
+                    .open(&mut open)
+                    .show(ctx, |ui| contents(ui))
+                    .and_then(|window| window.inner);
+                return (result, !open);
+            }
+            let result = egui::CentralPanel::default()
+                .show(ctx, |ui| contents(ui))
+                .inner;
+            (Some(result), ctx.input(|i| i.viewport().close_requested()))
+        })
+    }
+
+    /// Notes where the detached windows are now
+    pub fn track(&mut self, ctx: &egui::Context) {
+        for (panel, place) in &mut self.windows {
+            let (position, size) = ctx.input_for(panel.viewport_id(), |i| {
+                let viewport = i.viewport();
+                (
+                    viewport.outer_rect.map(|r| r.min),
+                    viewport.inner_rect.map(|r| r.size()),
+                )
+            });
+            if let Some(position) = position {
+                place.position = Some([position.x, position.y]);
+            }
+            if let Some(size) = size {
+                place.size = [size.x, size.y];
+            }
+        }
+    }
+
+    /// Writes the layout to storage
+    pub fn save(&self) {
+        if let Err(e) = self.write() {
+            eprintln!("Could not save window layout: {}", e);
+        }
+    }
+
+    fn write(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(storage) = &self.storage else {
+            return Ok(());
+        };
+        storage.write(KEY, &serde_json::to_string_pretty(self)?)
+    }
+}
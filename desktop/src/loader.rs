@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use eframe::egui;
+use game_core::format::MazeFormat;
+use game_core::{GameState, MazeFile, TextStyle};
+
+/// A maze ready to play, with the style its font comes from
+pub struct LoadedMaze {
+    pub state: GameState,
+    pub style: Option<TextStyle>,
+}
+
+/// Loads and checks a maze file on a background thread so big mazes
+/// don't freeze the window.
+pub struct MazeLoader {
+    path: PathBuf,
+    result: Receiver<Result<LoadedMaze, String>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl MazeLoader {
+    pub fn spawn(path: PathBuf, ctx: &egui::Context) -> Self {
+        let (sender, result) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let thread_path = path.clone();
+        let thread_cancelled = cancelled.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let loaded = load(&thread_path);
+            // Nobody is waiting for a cancelled load
+            if !thread_cancelled.load(Ordering::Relaxed) {
+                let _ = sender.send(loaded);
+                ctx.request_repaint();
+            }
+        });
+
+        Self {
+            path,
+            result,
+            cancelled,
+        }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Name of the file being loaded, for messages and saves
+    pub fn file_name(&self) -> String {
+        match self.path.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => self.path.display().to_string(),
+        }
+    }
+
+    /// The loaded maze, once the background thread is done
+    pub fn poll(&self) -> Option<Result<LoadedMaze, String>> {
+        match self.result.try_recv() {
+            Ok(loaded) => Some(loaded),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err("loading thread stopped".to_string())),
+        }
+    }
+
+    /// Stops waiting for the maze. The thread finishes its current read
+    /// and throws the result away.
+    pub fn cancel(self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The `maze.*` file in `dir`, trying JSON first and then each other
+/// format this build can read
+pub fn find_maze_file(dir: &Path) -> Option<PathBuf> {
+    MazeFormat::ALL
+        .into_iter()
+        .filter(|format| format.is_enabled())
+        .flat_map(|format| format.extensions())
+        .map(|extension| dir.join(format!("maze.{}", extension)))
+        .find(|path| path.exists())
+}
+
+fn load(path: &PathBuf) -> Result<LoadedMaze, String> {
+    let maze = MazeFile::load(path).map_err(|e| e.to_string())?;
+    // Problems a game survives are only worth a warning
+    for issue in maze.validate() {
+        eprintln!("Warning: {}", issue);
+    }
+    let style = maze.style.clone();
+    let state = GameState::from_maze(maze).map_err(|e| e.to_string())?;
+    Ok(LoadedMaze { state, style })
+}
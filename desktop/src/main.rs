@@ -1,12 +1,93 @@
+mod achievements;
+mod assets;
+mod attract;
+mod audio;
+mod autosave;
+mod big_picture;
+mod collection;
+mod cutscene;
+mod deep_link;
+mod dock;
+mod effects;
+mod feedback;
+mod fonts;
+mod ghost;
+mod history;
+mod layout;
+mod loader;
+mod options;
+mod paths;
+mod scanning;
+mod screenshot;
+mod settings;
+mod touch;
+#[cfg(feature = "update-check")]
+mod update;
+mod wellness;
+mod widgets;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use achievements::Achievements;
+use attract::AttractMode;
+use audio::Audio;
+use autosave::Autosave;
+use big_picture::BigPicture;
+use collection::Cards;
+use cutscene::CutscenePlayer;
+use deep_link::LaunchTarget;
+use effects::Effects;
 use eframe::{App, egui};
-use game_core::GameState;
+use egui_dock::{DockArea, DockState};
+use game_core::action::Action;
+use game_core::event::GameEvent;
+use game_core::feedback::Rating;
+use game_core::generate;
+use game_core::save::SaveGame;
+use game_core::storage::{FileStorage, MemoryStorage, Storage};
+use game_core::timeline::Timeline;
+use game_core::{GameState, Phase};
+use ghost::GhostRace;
+use history::Scrollback;
+use layout::{Layout, Panel};
+use loader::MazeLoader;
+use options::LaunchOptions;
+use paths::AppDirs;
+use scanning::Scanner;
+use screenshot::Screenshots;
+use settings::SettingsWindow;
+use touch::{Swipe, TouchMode};
+use wellness::{BreakOutcome, BreakReminder};
+
+/// How long the win screen stays up in kiosk mode before restarting
+const KIOSK_RESTART_DELAY: Duration = Duration::from_secs(15);
+
+/// Rooms kept in the journal unless --history says otherwise
+const JOURNAL_LENGTH: usize = 100;
+
+/// Seconds left from which the countdown turns red
+const TIME_WARNING_SECS: u64 = 10;
+
+/// Most time one frame may take off the maze's clock
+const MAX_TICK: Duration = Duration::from_secs(1);
+
+/// Where ratings are kept, one per line
+const RATINGS_KEY: &str = "ratings.jsonl";
 
 fn main() -> eframe::Result<()> {
-    let options = eframe::NativeOptions::default();
+    let launch = LaunchOptions::from_args();
+
+    let mut options = eframe::NativeOptions::default();
+    if launch.kiosk {
+        options.viewport = options.viewport.with_fullscreen(true);
+    }
+
     eframe::run_native(
         "Maze Game",
         options,
-        Box::new(|_cc| Ok(Box::new(MazeApp::default()))),
+        Box::new(|cc| Ok(Box::new(MazeApp::new(&cc.egui_ctx, launch)))),
     )
 }
 
@@ -17,68 +98,922 @@ fn main() -> eframe::Result<()> {
 enum GameAction {
     /// Start a new game
     Restart,
-    /// Choose an exit at the given index
-    ChooseExit(usize),
+    /// Play a freshly generated square maze with this many rooms a side
+    NewRandomMaze(usize),
+    /// Something the player does in the game itself
+    Play(Action),
+    /// Point out the exit towards the nearest end
+    Hint,
+    /// Take back the last action
+    Undo,
+    /// Do the last undone action again
+    Redo,
+    /// Jump to a step of the timeline
+    Rewind(usize),
+    /// Pop a panel out into its own window, or back in
+    Detach(Panel, bool),
+    /// Bring a panel up in the compact touch layout
+    ShowPanel(Panel),
+    /// Switch the controller-first profile on or off
+    ToggleBigPicture,
+    /// Open the settings window
+    OpenSettings,
+    /// Show or hide author commentary
+    ToggleCommentary,
+    /// Save the player's stars and comment for this maze
+    Rate(u8, String),
 }
 
 struct MazeApp {
     state: GameState,
+    options: LaunchOptions,
+    /// When the player reached an end room, for the kiosk auto-restart
+    finished_at: Option<Instant>,
+    /// Self-playing demo, while the app is idle
+    attract: Option<AttractMode>,
+    last_input: Instant,
+    /// When the maze's clock last advanced
+    last_tick: Instant,
+    break_reminder: Option<BreakReminder>,
+    /// Shown in screenshot annotations
+    maze_name: String,
+    screenshots: Screenshots,
+    /// Font for room descriptions, from the maze's style
+    description_font: Option<egui::FontId>,
+    /// Switch-access scanning, when enabled
+    scanner: Option<Scanner>,
+    /// Descriptions of the rooms passed through, for the journal
+    scrollback: Scrollback,
+    /// Layout for tablets and phones, once touch is in use
+    touch: TouchMode,
+    /// Profile for playing with a controller
+    big_picture: BigPicture,
+    /// Timing and images for the cutscene playing
+    cutscene: CutscenePlayer,
+    /// The maze's sound effects, ambience and music
+    audio: Audio,
+    /// Animations and flashes, within the player's safety settings
+    effects: Effects,
+    /// What the maze's scripts printed on the last move
+    script_output: Vec<String>,
+    /// Author commentary turned on from the results screen
+    commentary: bool,
+    /// Where ratings, ghosts and the autosave are kept
+    storage: Arc<dyn Storage>,
+    /// Whether the player rated this run
+    rated: bool,
+    /// Folder holding the maze file and its assets
+    maze_dir: PathBuf,
+    /// Maze file still being read in the background
+    loading: Option<MazeLoader>,
+    /// Background saving of the game, off in kiosk mode
+    autosave: Option<Autosave>,
+    /// Why the maze file could not be used, until the player dismisses it
+    load_error: Option<(String, String)>,
+    /// Every step played, with --dev
+    timeline: Option<Timeline>,
+    /// This attempt against the best one, unless off or in kiosk mode
+    ghost: Option<GhostRace>,
+    /// Unlocked achievements and toasts for new ones
+    achievements: Achievements,
+    /// Rooms found across every maze played
+    cards: Cards,
+    /// The player's preferences and the window to change them
+    settings: SettingsWindow,
+    /// Room and exit of the last hint given
+    hint: Option<(String, usize)>,
+    /// How the panels are docked or popped out
+    layout: Layout,
+    /// Looking for a newer release, unless turned off
+    #[cfg(feature = "update-check")]
+    update: Option<update::UpdateCheck>,
 }
 
-impl Default for MazeApp {
-    fn default() -> Self {
-        // Try to load maze.json from the executable's directory
-        let exe_dir = std::env::current_exe()
-            .ok()
-            .and_then(|path| path.parent().map(|p| p.to_owned()));
-
-        let state = if let Some(dir) = exe_dir {
-            let maze_path = dir.join("maze.json");
-            if maze_path.exists() {
-                match GameState::load_from_file(&maze_path) {
-                    Ok(state) => state,
-                    Err(e) => {
-                        eprintln!("Error loading maze.json: {}. Using default maze.", e);
-                        GameState::new()
-                    }
+impl MazeApp {
+    fn new(ctx: &egui::Context, options: LaunchOptions) -> Self {
+        let dirs = AppDirs::locate(options.portable);
+
+        let target = options
+            .open
+            .as_deref()
+            .and_then(|arg| match LaunchTarget::parse(arg) {
+                Ok(target) => Some(target),
+                Err(e) => {
+                    eprintln!("Cannot open {}: {}", arg, e);
+                    None
                 }
+            });
+
+        // Read the maze file in the background, falling back to the
+        // built-in maze. Unless told otherwise it sits next to the executable.
+        let (maze_path, random) = match target {
+            Some(LaunchTarget::File(path)) => (Some(path), None),
+            Some(LaunchTarget::Random {
+                seed,
+                width,
+                height,
+            }) => (None, Some((seed, width, height))),
+            None => (loader::find_maze_file(&dirs.exe), None),
+        };
+        let maze_dir = maze_path
+            .as_ref()
+            .and_then(|path| path.parent())
+            .map_or_else(|| dirs.exe.clone(), |dir| dir.to_owned());
+        let loading = maze_path.map(|path| MazeLoader::spawn(path, ctx));
+        let state = GameState::default();
+
+        let storage: Arc<dyn Storage> = Arc::new(FileStorage::new(&dirs.data));
+        // Kiosks are shared, so a game left there should not come back
+        let autosave = (!options.kiosk).then(|| Autosave::spawn(storage.clone(), ctx));
+        // and unlocks and finds there belong to nobody
+        let player_storage: Arc<dyn Storage> = if options.kiosk {
+            Arc::new(MemoryStorage::default())
+        } else {
+            storage.clone()
+        };
+
+        // Kiosks have nobody to install an update
+        #[cfg(feature = "update-check")]
+        let update =
+            (!options.no_update_check && !options.kiosk).then(|| update::UpdateCheck::spawn(ctx));
+
+        let mut app = Self {
+            state,
+            finished_at: None,
+            attract: None,
+            last_input: Instant::now(),
+            last_tick: Instant::now(),
+            scanner: if options.two_switch {
+                Some(Scanner::two_switch())
             } else {
-                GameState::new()
-            }
+                options.scan_interval.map(Scanner::one_switch)
+            },
+            scrollback: Scrollback::new(options.history.unwrap_or(JOURNAL_LENGTH)),
+            script_output: Vec::new(),
+            touch: TouchMode::new(options.touch),
+            big_picture: BigPicture::new(options.big_picture),
+            cutscene: CutscenePlayer::new(),
+            audio: Audio::new(),
+            effects: Effects::new(
+                options.reduced_motion,
+                options
+                    .max_flash_rate
+                    .unwrap_or(effects::FLASH_RATE_CEILING),
+            ),
+            commentary: options.commentary,
+            rated: false,
+            break_reminder: options
+                .break_after
+                .map(|limit| BreakReminder::new(limit, options.kiosk)),
+            achievements: Achievements::load(player_storage.clone()),
+            cards: Cards::load(player_storage),
+            options,
+            maze_name: "Built-in maze".to_string(),
+            screenshots: Screenshots::new(dirs.data.join("screenshots")),
+            description_font: None,
+            ghost: None,
+            hint: None,
+            layout: Layout::load(storage.clone()),
+            settings: SettingsWindow::load(storage.clone(), ctx),
+            storage,
+            #[cfg(feature = "update-check")]
+            update,
+            maze_dir,
+            loading,
+            autosave,
+            load_error: None,
+            timeline: None,
+        };
+        if let Some((seed, width, height)) = random {
+            app.play_random_maze(seed, width, height);
+        } else if app.loading.is_none() {
+            app.resume();
+        }
+        app.reset_timeline();
+        app.reset_ghost();
+        app
+    }
+
+    /// Start the timeline over from the current state
+    fn reset_timeline(&mut self) {
+        if self.options.dev {
+            self.timeline = Some(Timeline::new(&self.state));
+        }
+    }
+
+    /// Start a new attempt against the best run of this maze
+    fn reset_ghost(&mut self) {
+        // A resumed game has lost its timings, so it cannot set a best
+        let fresh = self.state.play.visited.len() <= 1;
+        if !self.options.no_ghost && !self.options.kiosk && fresh {
+            let start = &self.state.play.current_room;
+            self.ghost = Some(GhostRace::new(self.storage.clone(), &self.maze_name, start));
         } else {
-            GameState::new()
+            self.ghost = None;
+        }
+    }
+
+    /// Continue an unfinished autosaved game on this maze, if there is one
+    fn resume(&mut self) {
+        if self.autosave.is_none() {
+            return;
+        }
+        let Ok(save) = SaveGame::load(&*self.storage, autosave::KEY) else {
+            return;
+        };
+        let same_rooms = save.state.maze.rooms.iter().map(|r| &r.id).eq(self
+            .state
+            .maze
+            .rooms
+            .iter()
+            .map(|r| &r.id));
+        let playing = !save.state.play.is_finished() && !save.state.play.is_game_over();
+        if save.maze == self.maze_name && same_rooms && playing {
+            self.state.play = save.state.play;
+        }
+    }
+
+    /// Show the loading screen and take the maze once it arrives.
+    /// Returns true while still loading.
+    fn update_loading(&mut self, ctx: &egui::Context) -> bool {
+        let Some(loader) = &self.loading else {
+            return false;
+        };
+
+        let Some(loaded) = loader.poll() else {
+            let mut cancel = false;
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(40.0);
+                    ui.spinner();
+                    ui.label(format!("Loading {}…", loader.path().display()));
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+            if cancel {
+                eprintln!("Loading cancelled. Using default maze.");
+                if let Some(loader) = self.loading.take() {
+                    loader.cancel();
+                }
+                self.resume();
+                self.reset_timeline();
+                self.reset_ghost();
+                return false;
+            }
+            return true;
+        };
+        let file_name = loader.file_name();
+        self.loading = None;
+
+        match loaded {
+            Ok(maze) => {
+                self.description_font =
+                    fonts::description_font(ctx, maze.style.as_ref(), &self.maze_dir);
+                self.audio.stop();
+                self.maze_name = file_name;
+                self.state = maze.state;
+                self.state.reseed(time_seed());
+                self.cards.visit(&self.state, &self.maze_name);
+                self.last_input = Instant::now();
+            }
+            Err(e) => {
+                eprintln!("Error loading {}: {}. Using default maze.", file_name, e);
+                // A kiosk has nobody at hand to dismiss the dialog
+                if !self.options.kiosk {
+                    self.load_error = Some((file_name, e));
+                }
+            }
+        }
+        self.resume();
+        self.reset_timeline();
+        self.reset_ghost();
+        false
+    }
+
+    /// Tell the player why their maze was not loaded
+    fn show_load_error(&mut self, ctx: &egui::Context) {
+        let Some((file_name, error)) = &self.load_error else {
+            return;
         };
 
-        Self { state }
+        let mut dismissed = false;
+        egui::Modal::new(egui::Id::new("load_error")).show(ctx, |ui| {
+            ui.set_max_width(400.0);
+            ui.heading(format!("Could not load {}", file_name));
+            ui.label(error.as_str());
+            ui.label("Playing the built-in maze instead.");
+            ui.add_space(10.0);
+            dismissed = ui.button("OK").clicked();
+        });
+        if dismissed {
+            self.load_error = None;
+        }
+    }
+
+    /// Commentary is for players who have finished, or for reviewers
+    fn show_commentary(&self) -> bool {
+        self.commentary && (self.state.play.is_finished() || self.options.commentary)
+    }
+
+    /// Whether the room's exits are on screen to be picked
+    fn exits_shown(&self) -> bool {
+        self.state.can_act()
+    }
+
+    /// Where the exit picked with a number key is kept, for this room
+    /// and step only
+    fn selected_exit_id(&self) -> egui::Id {
+        let play = &self.state.play;
+        egui::Id::new(("selected_exit", &play.current_room, play.steps))
+    }
+
+    /// The exit picked with a number key, waiting for Enter
+    fn selected_exit(&self, ctx: &egui::Context) -> Option<usize> {
+        ctx.data(|d| d.get_temp(self.selected_exit_id()))
+    }
+
+    /// Index of the choice highlighted by switch scanning, if active
+    fn scan_highlight(&self) -> Option<usize> {
+        if self.attract.is_some() {
+            return None;
+        }
+        self.scanner.as_ref().map(Scanner::highlighted)
     }
-}
 
-impl MazeApp {
     /// Render the game UI and collect any user actions.
-    /// This function only reads state, never modifies it.
-    fn render_ui(&self, ctx: &egui::Context) -> Option<GameAction> {
+    /// This function only reads game state, never modifies it; `dock` is
+    /// just the arrangement of the panels.
+    fn render_ui(&self, ctx: &egui::Context, dock: &mut DockState<Panel>) -> Option<GameAction> {
         let mut action = None;
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("🧱 Maze Game");
-            ui.separator();
+        // Kiosk visitors get the maze they were given
+        if self.attract.is_none() && !self.options.kiosk {
+            action = render_menu(ctx, self.big_picture.is_active());
+        }
+        if self.attract.is_none() {
+            action = self.render_detached(ctx).or(action);
+        }
+        if self.attract.is_none() && self.state.is_roaming() {
+            action = self.render_room_list(ctx).or(action);
+        }
+        if let Some(timeline) = &self.timeline {
+            action = self.render_debug(ctx, timeline).or(action);
+        }
 
-            let room = self.state.current_room();
-            ui.label(room.description.clone());
-            ui.add_space(20.0);
+        // Portrait screens are too narrow to dock panels side by side, and
+        // dock tabs cannot be reached with a controller
+        if self.touch.is_compact(ctx) || self.big_picture.is_active() {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let tapped = self.touch.tab_row(ui).map(GameAction::ShowPanel);
+                ui.separator();
+                let played = self.render_panel(ui, self.touch.shown);
+                action = played.or(tapped).or(action.take());
+            });
+        } else {
+            let mut tabs = dock::Tabs {
+                app: self,
+                action: None,
+            };
+            DockArea::new(dock)
+                .style(egui_dock::Style::from_egui(ctx.style().as_ref()))
+                .show_leaf_close_all_buttons(false)
+                .show_leaf_collapse_buttons(false)
+                // Dragging a tab would also read as a swipe
+                .draggable_tabs(!self.options.kiosk && !self.touch.is_active())
+                .show(ctx, &mut tabs);
+            action = tabs.action.or(action);
+        }
 
-            if room.is_end {
-                ui.label("🎉 You reached the end of the maze!");
-                if ui.button("Restart").clicked() {
-                    action = Some(GameAction::Restart);
+        // Keyboard shortcuts, unless the player is typing somewhere
+        if action.is_none() && self.attract.is_none() && !ctx.wants_keyboard_input() {
+            let open = self.state.available_exits();
+            let selected = self.selected_exit(ctx).filter(|exit| open.contains(exit));
+            let hotkeys = self.settings.settings.hotkeys;
+            let number = ctx.input(|i| widgets::NUMBER_KEYS.iter().position(|k| i.key_pressed(*k)));
+            if let Some(&exit) = number.and_then(|n| open.get(n))
+                && hotkeys
+                && self.exits_shown()
+            {
+                let id = self.selected_exit_id();
+                ctx.data_mut(|d| d.insert_temp(id, exit));
+                ctx.request_repaint();
+            }
+
+            action = ctx.input(|i| {
+                let ctrl = i.modifiers.command;
+                if let Some(exit) = selected
+                    && hotkeys
+                    && i.key_pressed(egui::Key::Enter)
+                {
+                    Some(GameAction::Play(Action::ChooseExit(exit)))
+                } else if hotkeys && i.modifiers.is_none() && i.key_pressed(egui::Key::R) {
+                    Some(GameAction::Restart)
+                } else if i.key_pressed(egui::Key::Backspace)
+                    || (ctrl && !i.modifiers.shift && i.key_pressed(egui::Key::Z))
+                {
+                    Some(GameAction::Undo)
+                } else if ctrl
+                    && (i.key_pressed(egui::Key::Y)
+                        || (i.modifiers.shift && i.key_pressed(egui::Key::Z)))
+                {
+                    Some(GameAction::Redo)
+                } else {
+                    None
                 }
+            });
+        }
+
+        action
+    }
+
+    /// What a panel shows, wherever it is
+    fn render_panel(&self, ui: &mut egui::Ui, panel: Panel) -> Option<GameAction> {
+        match panel {
+            Panel::Play => self.render_play(ui),
+            Panel::Inventory => self.render_inventory(ui),
+            Panel::Journal => {
+                self.scrollback
+                    .show_window(ui, self.description_font.as_ref());
+                None
+            }
+            Panel::Stats => {
+                self.render_stats(ui);
+                None
+            }
+            Panel::Achievements => {
+                self.achievements.show_panel(ui);
+                None
+            }
+            Panel::Collection => {
+                self.cards.show_panel(ui);
+                None
+            }
+            Panel::Map => {
+                widgets::minimap(ui, &self.state.minimap());
+                None
+            }
+        }
+    }
+
+    /// The room, its exits and the end screen
+    fn render_play(&self, ui: &mut egui::Ui) -> Option<GameAction> {
+        let mut action = None;
+
+        ui.heading("🧱 Maze Game");
+        if self.attract.is_none()
+            && let Some(ghost) = &self.ghost
+        {
+            ghost.show(ui);
+        }
+        if self.attract.is_none()
+            && let Some(left) = self.state.time_left()
+        {
+            let secs = left.as_secs_f32().ceil() as u64;
+            let clock = self.settings.locale().duration(Duration::from_secs(secs));
+            let text = egui::RichText::new(format!("⏱ {}", clock)).strong();
+            if secs <= TIME_WARNING_SECS {
+                ui.label(text.color(ui.visuals().error_fg_color));
             } else {
-                for (i, exit) in room.exits.iter().enumerate() {
-                    if ui.button(exit.label.clone()).clicked() {
-                        action = Some(GameAction::ChooseExit(i));
+                ui.label(text);
+            }
+        }
+        ui.separator();
+
+        // While idle, show the demo instead of the player's session
+        let state = self
+            .attract
+            .as_ref()
+            .map_or(&self.state, AttractMode::state);
+        if self.attract.is_some() {
+            ui.strong("▶ Demo — press any key to play");
+            ui.add_space(10.0);
+        }
+
+        let view = state.view();
+        let room = view.room;
+        let highlight = self.scan_highlight();
+
+        // A cutscene takes the place of the room until it is over
+        if self.attract.is_none()
+            && let Some(cutscene) = &view.cutscene
+        {
+            return self
+                .cutscene
+                .show(ui, cutscene, highlight)
+                .map(GameAction::Play);
+        }
+        let direction = state.maze.text_direction();
+        if self.attract.is_none() && self.options.history.is_some() {
+            widgets::maze_text(ui, direction, |ui| {
+                self.scrollback.show(ui, self.description_font.as_ref())
+            });
+        }
+        widgets::maze_text(ui, direction, |ui| match &self.description_font {
+            Some(font) => ui.label(egui::RichText::new(&view.description).font(font.clone())),
+            None => ui.label(view.description.clone()),
+        });
+        if self.attract.is_none() {
+            for line in &self.script_output {
+                ui.label(egui::RichText::new(line).italics());
+            }
+        }
+        if self.attract.is_none()
+            && self.show_commentary()
+            && let Some(commentary) = &room.commentary
+        {
+            ui.add_space(10.0);
+            egui::Frame::group(ui.style()).show(ui, |ui| {
+                ui.strong("💬 Author's commentary");
+                ui.label(egui::RichText::new(commentary).italics());
+            });
+        }
+
+        if self.attract.is_none() {
+            for (item, take) in &view.items {
+                ui.horizontal(|ui| {
+                    let label = ui.label(format!("You see {}.", item.name));
+                    if let Some(description) = &item.description {
+                        label.on_hover_text(description);
+                    }
+                    if let Some(action_taken) = widgets::choice(ui, take, "Pick up") {
+                        action = Some(GameAction::Play(action_taken));
+                    }
+                });
+            }
+            for (npc, talk) in &view.npcs {
+                ui.horizontal(|ui| {
+                    let label = ui.label(format!("{} is here.", npc.name));
+                    if let Some(description) = &npc.description {
+                        label.on_hover_text(description);
+                    }
+                    if let Some(action_taken) = widgets::choice(ui, talk, "Talk") {
+                        action = Some(GameAction::Play(action_taken));
                     }
+                });
+            }
+            if let Some(conversation) = &view.conversation
+                && let Some(action_taken) = widgets::dialogue(ui, conversation)
+            {
+                action = Some(GameAction::Play(action_taken));
+            }
+            if let Some(combat) = &view.combat
+                && let Some(action_taken) = widgets::fight(ui, combat, view.stats, highlight)
+            {
+                action = Some(GameAction::Play(action_taken));
+            }
+
+            let bookmarked = self.state.is_bookmarked(&room.id);
+            let text = if bookmarked {
+                "★ Bookmarked"
+            } else {
+                "☆ Bookmark"
+            };
+            if ui.selectable_label(bookmarked, text).clicked() {
+                action = Some(GameAction::Play(Action::ToggleBookmark(room.id.clone())));
+            }
+        }
+        ui.add_space(20.0);
+
+        if let Phase::GameOver { cause } = &state.play.phase {
+            let lost = egui::RichText::new("💀 Game over").color(ui.visuals().error_fg_color);
+            ui.heading(lost);
+            ui.label(format!("Lost to {}.", cause));
+            if let Some(finished_at) = self.finished_at {
+                let remaining = KIOSK_RESTART_DELAY.saturating_sub(finished_at.elapsed());
+                let secs = self
+                    .settings
+                    .locale()
+                    .number(remaining.as_secs() as i64 + 1);
+                ui.label(format!("Restarting in {} s", secs));
+            } else if ui
+                .add(widgets::choice_button(
+                    ui,
+                    "Try again",
+                    highlight == Some(0),
+                ))
+                .on_hover_text("R")
+                .clicked()
+            {
+                action = Some(GameAction::Restart);
+            }
+        } else if room.is_end {
+            if state.is_roaming() {
+                match &room.ending {
+                    Some(ending) => ui.label(format!(
+                        "{} One of the maze's endings: {}.",
+                        widgets::ending_glyph(ending.kind),
+                        ending.title
+                    )),
+                    None => ui.label("🎉 One of the maze's endings."),
+                };
+            } else if let Some(ending) = state.ending() {
+                widgets::ending(ui, &ending);
+            }
+            if let Some(finished_at) = self.finished_at {
+                let remaining = KIOSK_RESTART_DELAY.saturating_sub(finished_at.elapsed());
+                let secs = self
+                    .settings
+                    .locale()
+                    .number(remaining.as_secs() as i64 + 1);
+                ui.label(format!("Restarting in {} s", secs));
+            } else if ui
+                .add(widgets::choice_button(ui, "Restart", highlight == Some(0)))
+                .on_hover_text("R")
+                .clicked()
+            {
+                action = Some(GameAction::Restart);
+            }
+            if self.finished_at.is_none()
+                && self.attract.is_none()
+                && !state.is_roaming()
+                && ui.button("🗺 Free roam").clicked()
+            {
+                action = Some(GameAction::Play(Action::StartFreeRoam));
+            }
+            if self.finished_at.is_none()
+                && self.attract.is_none()
+                && state.maze.rooms.iter().any(|r| r.commentary.is_some())
+                && ui
+                    .selectable_label(self.commentary, "💬 Author commentary")
+                    .clicked()
+            {
+                action = Some(GameAction::ToggleCommentary);
+            }
+
+            if !state.is_roaming()
+                && let Some(score) = state.score()
+            {
+                ui.add_space(10.0);
+                widgets::score(ui, &score);
+            }
+
+            // Kiosks are shared, so ratings there would mean little
+            if self.finished_at.is_none() && self.attract.is_none() && !state.is_roaming() {
+                ui.add_space(10.0);
+                if self.rated {
+                    ui.label("Thanks for rating this maze!");
+                } else if let Some((stars, comment)) = feedback::rating_prompt(ui) {
+                    action = Some(GameAction::Rate(stars, comment));
+                }
+            }
+        } else if !state.in_combat() {
+            // Scanning steps through the open exits only
+            let open = state.available_exits();
+            let selected = self
+                .attract
+                .is_none()
+                .then(|| self.selected_exit(ui.ctx()))
+                .flatten();
+            let highlight = highlight.and_then(|h| open.get(h).copied()).or(selected);
+            let hint = self.hint_here();
+            // Grid mazes on a touchscreen get a pad instead of a list
+            let chosen = match room.compass_exits().filter(|_| self.touch.is_active()) {
+                Some(compass) => {
+                    let compass = compass.map(|exit| exit.filter(|i| open.contains(i)));
+                    widgets::dpad(ui, room, compass, highlight, hint)
+                }
+                None => {
+                    let closed: Vec<_> = state
+                        .shown_exits()
+                        .into_iter()
+                        .filter_map(|i| state.exit_closed(i).map(|closed| (i, closed)))
+                        .collect();
+                    let exits = widgets::ShownExits {
+                        open: &open,
+                        closed: &closed,
+                    };
+                    widgets::maze_text(ui, state.maze.text_direction(), |ui| {
+                        widgets::exit_buttons(ui, room, &exits, highlight, hint)
+                    })
                 }
+            };
+            if let Some(i) = chosen {
+                action = Some(GameAction::Play(Action::ChooseExit(i)));
+            }
+        }
+
+        if self.attract.is_none() && self.finished_at.is_none() {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                let back = ui
+                    .add_enabled(self.state.can_undo(), egui::Button::new("⟲ Go back"))
+                    .on_hover_text("Backspace or Ctrl+Z");
+                if back.clicked() {
+                    action = Some(GameAction::Undo);
+                }
+                let forward = ui
+                    .add_enabled(self.state.can_redo(), egui::Button::new("⟳ Redo"))
+                    .on_hover_text("Ctrl+Y");
+                if forward.clicked() {
+                    action = Some(GameAction::Redo);
+                }
+                let playing = !room.is_end && !self.state.play.is_game_over();
+                if playing && self.render_hint_button(ui) {
+                    action = Some(GameAction::Hint);
+                }
+                if self.state.can_reroll() {
+                    let left = self.settings.locale().number(self.state.play.rerolls);
+                    let text = format!("🎲 Reroll ({} left)", left);
+                    let reroll = ui
+                        .button(text)
+                        .on_hover_text("Take back the last random turn and try your luck again");
+                    if reroll.clicked() {
+                        action = Some(GameAction::Play(Action::Reroll));
+                    }
+                }
+            });
+        }
+
+        action
+    }
+
+    /// How far the player has got
+    fn render_stats(&self, ui: &mut egui::Ui) {
+        let stats = self.state.view().stats;
+        let locale = self.settings.locale();
+        let (visited, rooms) = (stats.rooms_visited as i64, stats.room_count as i64);
+        ui.label(format!(
+            "Rooms visited: {} of {}",
+            locale.number(visited),
+            locale.number(rooms)
+        ));
+        ui.label(format!(
+            "Items carried: {}",
+            locale.number(stats.items_carried as i64)
+        ));
+        ui.label(format!("Steps: {}", locale.number(stats.steps)));
+        if self.state.uses_health() {
+            let (health, max) = (stats.health, stats.max_health);
+            ui.label(format!(
+                "Health: {} of {}",
+                locale.number(health),
+                locale.number(max)
+            ));
+            let (stamina, max) = (stats.stamina, stats.max_stamina);
+            ui.label(format!(
+                "Stamina: {} of {}",
+                locale.number(stamina),
+                locale.number(max)
+            ));
+        }
+        if self.state.uses_luck() {
+            ui.label(format!(
+                "Luck: {}",
+                locale.number(self.state.maze.player.luck)
+            ));
+            ui.label(format!(
+                "Rerolls: {}",
+                locale.number(self.state.play.rerolls)
+            ));
+        }
+        if self.state.play.hints_used > 0 {
+            ui.label(format!(
+                "Hints used: {}",
+                locale.number(self.state.play.hints_used)
+            ));
+        }
+    }
+
+    /// The hinted exit, if the hint was for the room the player is in
+    fn hint_here(&self) -> Option<usize> {
+        match &self.hint {
+            Some((room, exit)) if *room == self.state.play.current_room => Some(*exit),
+            _ => None,
+        }
+    }
+
+    /// Hint button, greyed out once the hints run out.
+    /// Returns true if clicked.
+    fn render_hint_button(&self, ui: &mut egui::Ui) -> bool {
+        let left = self
+            .options
+            .hint_limit
+            .map(|limit| limit.saturating_sub(self.state.play.hints_used as usize));
+        let text = match left {
+            Some(left) => {
+                let left = self.settings.locale().number(left as i64);
+                format!("💡 Hint ({} left)", left)
+            }
+            None => "💡 Hint".to_string(),
+        };
+        let enabled = left != Some(0) && self.hint_here().is_none();
+        ui.add_enabled(enabled, egui::Button::new(text))
+            .on_hover_text("Show which way leads towards the end")
+            .on_disabled_hover_text("No hints left")
+            .clicked()
+    }
+
+    /// Windows for the panels the player popped out
+    fn render_detached(&self, ctx: &egui::Context) -> Option<GameAction> {
+        let mut action = None;
+
+        for panel in self.layout.detached() {
+            let (chosen, closed) = self
+                .layout
+                .show(ctx, panel, |ui| self.render_panel(ui, panel));
+            action = chosen.flatten().or(action);
+            if closed {
+                action = Some(GameAction::Detach(panel, false));
             }
+        }
+
+        action
+    }
+
+    /// The inventory and bookmarks. Bookmarks are clickable when fast
+    /// travel is open.
+    fn render_inventory(&self, ui: &mut egui::Ui) -> Option<GameAction> {
+        let mut action = None;
+
+        let view = self.state.view();
+        if view.inventory.is_empty() {
+            ui.weak("You are not carrying anything.");
+        } else {
+            for (item, drop) in &view.inventory {
+                ui.horizontal(|ui| {
+                    let label = ui.label(&item.name);
+                    if let Some(description) = &item.description {
+                        label.on_hover_text(description);
+                    }
+                    if let Some(action_taken) = widgets::choice(ui, drop, "Drop") {
+                        action = Some(GameAction::Play(action_taken));
+                    }
+                });
+            }
+        }
+
+        if self.state.play.bookmarks.is_empty() {
+            return action;
+        }
+        ui.separator();
+        ui.heading("Bookmarks");
+        let can_travel = self.state.can_fast_travel();
+        for id in &self.state.play.bookmarks {
+            let here = *id == self.state.play.current_room;
+            if ui
+                .add_enabled(can_travel && !here, egui::Button::new(id))
+                .clicked()
+            {
+                action = Some(GameAction::Play(Action::TravelTo(id.clone())));
+            }
+        }
+        if !can_travel {
+            ui.weak("Fast travel opens once you finish.");
+        }
+
+        action
+    }
+
+    /// Developer window for scrubbing through the timeline
+    fn render_debug(&self, ctx: &egui::Context, timeline: &Timeline) -> Option<GameAction> {
+        let mut step = timeline.position();
+
+        egui::Window::new("🛠 Debug")
+            .default_open(false)
+            .show(ctx, |ui| {
+                ui.label(format!("Room: {}", self.state.play.current_room));
+                ui.label(format!("Phase: {:?}", self.state.play.phase));
+                ui.label(format!("Inventory: {:?}", self.state.play.inventory));
+                ui.separator();
+                ui.add(egui::Slider::new(&mut step, 0..=timeline.len()).text("step"));
+            });
+
+        (step != timeline.position()).then_some(GameAction::Rewind(step))
+    }
+
+    /// Side panel listing every room with what the player missed there
+    fn render_room_list(&self, ctx: &egui::Context) -> Option<GameAction> {
+        let mut action = None;
+
+        egui::SidePanel::left("free_roam").show(ctx, |ui| {
+            ui.heading("All rooms");
+            ui.weak("Free roam: moves are not recorded.");
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for room in &self.state.maze.rooms {
+                    let Some(notes) = self.state.annotation(&room.id) else {
+                        continue;
+                    };
+                    let here = room.id == self.state.play.current_room;
+                    if ui.selectable_label(here, &room.id).clicked() && !here {
+                        action = Some(GameAction::Play(Action::RoamTo(room.id.clone())));
+                    }
+
+                    let mut details = Vec::new();
+                    if !notes.visited {
+                        details.push("never visited".to_string());
+                    }
+                    if notes.secret_exits > 0 {
+                        details.push(format!("{} secret exits", notes.secret_exits));
+                    }
+                    if notes.unexplored_exits > 0 {
+                        details.push(format!("{} unexplored exits", notes.unexplored_exits));
+                    }
+                    if !details.is_empty() {
+                        ui.weak(details.join(", "));
+                    }
+                }
+            });
         });
 
         action
@@ -87,21 +1022,396 @@ impl MazeApp {
     /// Update game state based on user actions.
     /// Only called when there are actions to process.
     fn update_state(&mut self, action: GameAction) {
+        let health = self.state.play.health;
+        self.apply_action(action);
+        if self.state.play.health < health {
+            self.effects.flash();
+        }
+        self.achievements.record(&self.state, &self.maze_name);
+        if let Some(ghost) = &mut self.ghost {
+            ghost.entered(&self.state.play.current_room);
+            if matches!(self.state.play.phase, Phase::Finished { .. }) {
+                ghost.finish();
+            }
+        }
+        if let Some(autosave) = &self.autosave {
+            autosave.save(SaveGame::new(&self.maze_name, self.state.clone()));
+        }
+    }
+
+    fn apply_action(&mut self, action: GameAction) {
         match action {
-            GameAction::Restart => self.state = GameState::new(),
-            GameAction::ChooseExit(i) => self.state.choose_exit(i),
+            GameAction::Restart => {
+                self.state.restart();
+                self.state.reseed(time_seed());
+                self.cards.visit(&self.state, &self.maze_name);
+                self.finished_at = None;
+                self.rated = false;
+                self.hint = None;
+                self.scrollback.clear();
+                self.script_output.clear();
+                self.reset_timeline();
+                self.reset_ghost();
+            }
+            GameAction::NewRandomMaze(size) => {
+                self.play_random_maze(time_seed(), size, size);
+            }
+            GameAction::Play(action) => {
+                let left = self.state.view().description;
+                if !self.state.apply(&action) {
+                    eprintln!("Ignoring {:?}: not possible right now", action);
+                } else if let Some(timeline) = &mut self.timeline {
+                    timeline.record(&self.state, action);
+                }
+                self.script_output = self.state.take_messages();
+                let events = self.state.take_events();
+                let volume = self.settings.settings.volume;
+                self.audio
+                    .play_events(&events, &self.state, &self.maze_dir, volume);
+                for event in events {
+                    match event {
+                        GameEvent::RoomEntered { .. } => {
+                            self.scrollback.record(&left);
+                            self.cards.visit(&self.state, &self.maze_name);
+                        }
+                        GameEvent::ExitBlocked(_) => {
+                            self.script_output.push("That way is closed.".to_string());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            GameAction::Hint => match self.state.use_hint() {
+                Some(exit) => {
+                    self.hint = Some((self.state.play.current_room.clone(), exit));
+                }
+                None => eprintln!("No hint: no end can be reached from here"),
+            },
+            GameAction::Undo => {
+                if self.state.undo() {
+                    self.after_undo();
+                }
+            }
+            GameAction::Redo => {
+                if self.state.redo() {
+                    self.after_undo();
+                }
+            }
+            GameAction::Rewind(step) => {
+                if let Some(timeline) = &mut self.timeline {
+                    timeline.rewind_to(&mut self.state, step);
+                    self.finished_at = None;
+                    // Replaying the steps ran their scripts again
+                    self.state.take_messages();
+                    self.script_output.clear();
+                }
+            }
+            GameAction::Detach(panel, detached) => self.layout.set_detached(panel, detached),
+            GameAction::ShowPanel(panel) => self.touch.shown = panel,
+            GameAction::ToggleBigPicture => self.big_picture.toggle(),
+            GameAction::OpenSettings => self.settings.open = true,
+            GameAction::ToggleCommentary => self.commentary = !self.commentary,
+            GameAction::Rate(stars, comment) => {
+                let saved = Rating::new(&self.maze_name, stars, &comment)
+                    .map(|rating| rating.append_to(&*self.storage, RATINGS_KEY));
+                match saved {
+                    Some(Ok(())) => self.rated = true,
+                    Some(Err(e)) => eprintln!("Could not save rating: {}", e),
+                    None => {}
+                }
+            }
         }
     }
+
+    /// Switch to a newly generated maze. The maze name is the link that
+    /// generates it again, so it can be shared.
+    fn play_random_maze(&mut self, seed: u64, width: usize, height: usize) {
+        match GameState::from_maze(generate::generate(seed, width, height)) {
+            Ok(state) => {
+                self.state = state;
+                self.maze_name = LaunchTarget::link(seed, width, height);
+                self.description_font = None;
+                self.audio.stop();
+                self.commentary = self.options.commentary;
+                self.apply_action(GameAction::Restart);
+            }
+            Err(e) => eprintln!("Generated maze is broken: {}", e),
+        }
+    }
+
+    /// After undo or redo, the game may have left the end room, and the
+    /// timeline, which only follows forward play, starts over
+    fn after_undo(&mut self) {
+        self.finished_at = None;
+        self.script_output.clear();
+        self.reset_timeline();
+    }
+
+    /// Swipe right to undo, and left to flip between the game and the
+    /// journal of rooms passed through
+    fn update_touch(&mut self, ctx: &egui::Context) -> Option<GameAction> {
+        match self.touch.update(ctx)? {
+            Swipe::Right => Some(GameAction::Undo),
+            Swipe::Left if self.touch.is_compact(ctx) => Some(GameAction::ShowPanel(
+                if self.touch.shown == Panel::Journal {
+                    Panel::Play
+                } else {
+                    Panel::Journal
+                },
+            )),
+            Swipe::Left => None,
+        }
+    }
+
+    /// Whether play is paused for a break
+    fn on_break(&self) -> bool {
+        self.break_reminder
+            .as_ref()
+            .is_some_and(|reminder| reminder.is_on_break())
+    }
+
+    /// Run the maze's clock, if it has one, and repaint as each second
+    /// passes so the countdown moves without input
+    fn update_timer(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        // A long stall, such as while loading, does not eat the clock
+        let elapsed = (now - std::mem::replace(&mut self.last_tick, now)).min(MAX_TICK);
+        if self.attract.is_some() || self.on_break() || self.state.time_left().is_none() {
+            return;
+        }
+
+        self.state.tick(elapsed);
+        let events = self.state.take_events();
+        let volume = self.settings.settings.volume;
+        self.audio
+            .play_events(&events, &self.state, &self.maze_dir, volume);
+        if events
+            .iter()
+            .any(|event| matches!(event, GameEvent::GameOver { .. }))
+        {
+            if let Some(autosave) = &self.autosave {
+                autosave.save(SaveGame::new(&self.maze_name, self.state.clone()));
+            }
+            return;
+        }
+        if let Some(left) = self.state.time_left()
+            && self.state.play.phase == Phase::Exploring
+        {
+            let to_next_second = left.saturating_sub(Duration::from_secs(left.as_secs()));
+            ctx.request_repaint_after(if to_next_second.is_zero() {
+                Duration::from_secs(1)
+            } else {
+                to_next_second
+            });
+        }
+    }
+
+    /// Run switch scanning and turn a selection into an action
+    fn update_scanner(&mut self, ctx: &egui::Context) -> Option<GameAction> {
+        let scanner = self.scanner.as_mut()?;
+        let room = self.state.current_room();
+        let open = self.state.available_exits();
+
+        let over = room.is_end || self.state.play.is_game_over();
+        let fighting = self.state.in_combat();
+        let watching = self.state.in_cutscene();
+
+        // In kiosk mode the end screens have a countdown instead of a button
+        let choices = match (over, self.finished_at) {
+            // Continue and skip
+            _ if watching => 2,
+            (true, Some(_)) => 0,
+            (true, None) => 1,
+            // Attack and flee
+            (false, _) if fighting => 2,
+            (false, _) => open.len(),
+        };
+
+        let selected = scanner.update(ctx, &room.id, choices)?;
+        Some(if watching {
+            GameAction::Play(if selected == 0 {
+                Action::NextBeat
+            } else {
+                Action::SkipCutscene
+            })
+        } else if over {
+            GameAction::Restart
+        } else if fighting {
+            GameAction::Play(if selected == 0 {
+                Action::Attack
+            } else {
+                Action::Flee
+            })
+        } else {
+            GameAction::Play(Action::ChooseExit(open[selected]))
+        })
+    }
+
+    /// Start, advance or stop the idle demo.
+    /// Returns true if input ended the demo this frame.
+    fn update_attract(&mut self, ctx: &egui::Context) -> bool {
+        let Some(idle_limit) = self.options.attract_after else {
+            return false;
+        };
+
+        if attract::had_input(ctx) {
+            return self.attract.take().is_some();
+        }
+
+        match &mut self.attract {
+            Some(demo) => demo.tick(ctx),
+            None => {
+                let idle = self.last_input.elapsed();
+                if idle >= idle_limit {
+                    self.attract =
+                        Some(AttractMode::new(GameState::start(self.state.maze.clone())));
+                    ctx.request_repaint();
+                } else {
+                    ctx.request_repaint_after(idle_limit - idle);
+                }
+            }
+        }
+        false
+    }
+
+    /// Keep the kiosk window open and restart a while after each win or loss.
+    fn update_kiosk(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| i.viewport().close_requested()) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        }
+
+        if !self.state.current_room().is_end && !self.state.play.is_game_over() {
+            return;
+        }
+
+        let finished_at = *self.finished_at.get_or_insert_with(Instant::now);
+        let elapsed = finished_at.elapsed();
+        if elapsed >= KIOSK_RESTART_DELAY {
+            self.update_state(GameAction::Restart);
+        } else {
+            // Tick the countdown without waiting for input
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+    }
+}
+
+/// A seed that differs from one run to the next
+fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Menu bar along the top of the window
+fn render_menu(ctx: &egui::Context, big_picture: bool) -> Option<GameAction> {
+    let mut action = None;
+
+    egui::TopBottomPanel::top("menu").show(ctx, |ui| {
+        egui::MenuBar::new().ui(ui, |ui| {
+            ui.menu_button("Game", |ui| {
+                let id = egui::Id::new("random_maze_size");
+                let mut size = ui.data_mut(|d| *d.get_temp_mut_or(id, 8usize));
+                ui.add(egui::Slider::new(&mut size, generate::SIZE_RANGE).text("rooms a side"));
+                ui.data_mut(|d| d.insert_temp(id, size));
+                if ui.button("🎲 New random maze").clicked() {
+                    action = Some(GameAction::NewRandomMaze(size));
+                    ui.close();
+                }
+            });
+            ui.menu_button("View", |ui| {
+                if ui.selectable_label(big_picture, "🎮 Big picture").clicked() {
+                    action = Some(GameAction::ToggleBigPicture);
+                    ui.close();
+                }
+                if ui.button("⚙ Settings…").clicked() {
+                    action = Some(GameAction::OpenSettings);
+                    ui.close();
+                }
+            });
+        });
+    });
+
+    action
 }
 
 impl App for MazeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if self.update_loading(ctx) {
+            return;
+        }
+        self.show_load_error(ctx);
+
+        if attract::had_input(ctx) {
+            self.last_input = Instant::now();
+        }
+
+        // Input that ends the demo must not also count as a move
+        let woke = self.update_attract(ctx);
+        self.update_timer(ctx);
+
+        self.big_picture.update(ctx);
+        self.effects.update(ctx);
+        let scanned = self.update_scanner(ctx);
+        let swiped = self.update_touch(ctx);
+        let timed = self
+            .cutscene
+            .update(ctx, &self.state, &self.maze_dir)
+            .map(GameAction::Play);
+        let volume = self.settings.settings.volume;
+        self.audio.update(ctx, &self.state, &self.maze_dir, volume);
+
         // First collect any actions using only immutable access
-        let action = self.render_ui(ctx);
+        let mut dock = std::mem::replace(&mut self.layout.dock, DockState::new(Vec::new()));
+        let action = self
+            .render_ui(ctx, &mut dock)
+            .or(scanned)
+            .or(swiped)
+            .or(timed);
+        self.layout.dock = dock;
 
-        // Then update state if we have an action
-        if let Some(_action) = action {
+        // Then update state if we have an action. The break prompt only
+        // blocks the mouse, so keys and gamepads are ignored here too.
+        if let Some(_action) = action
+            && self.attract.is_none()
+            && !woke
+            && !self.on_break()
+        {
             self.update_state(_action);
         }
+
+        if self.options.kiosk {
+            self.update_kiosk(ctx);
+        }
+
+        let annotation = [
+            format!("Maze: {}", self.maze_name),
+            format!("Room: {}", self.state.play.current_room),
+        ];
+        self.screenshots
+            .update(ctx, &annotation, self.settings.locale());
+        self.achievements.show_toast(ctx);
+        self.settings.show(ctx);
+        self.layout.track(ctx);
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.layout.save();
+        }
+        if let Some(autosave) = &mut self.autosave {
+            autosave.show_errors(ctx);
+        }
+        #[cfg(feature = "update-check")]
+        if let Some(update) = &mut self.update {
+            update.show(ctx);
+        }
+
+        if let Some(reminder) = &mut self.break_reminder
+            && let BreakOutcome::Restart = reminder.show(ctx, self.last_input)
+        {
+            self.update_state(GameAction::Restart);
+        }
+    }
+
+    fn raw_input_hook(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        self.big_picture.feed(ctx, raw_input);
     }
 }
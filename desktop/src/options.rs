@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+/// Options given on the command line at launch.
+#[derive(Debug, Default, Clone)]
+pub struct LaunchOptions {
+    /// Locked-down mode for museums and classrooms
+    pub kiosk: bool,
+    /// Idle time before the self-playing demo starts
+    pub attract_after: Option<Duration>,
+    /// Continuous play time before suggesting a break
+    pub break_after: Option<Duration>,
+    /// Switch scanning with the highlight advancing at this interval
+    pub scan_interval: Option<Duration>,
+    /// Switch scanning with one key to advance and one to select
+    pub two_switch: bool,
+    /// Keep this many earlier room descriptions on screen
+    pub history: Option<usize>,
+    /// Show author commentary from the start, for reviewing a maze
+    pub commentary: bool,
+    /// Developer tools, such as the timeline for scrubbing through play.
+    /// Never on in kiosk mode.
+    pub dev: bool,
+    /// Don't race against or record best runs
+    pub no_ghost: bool,
+    /// Don't look for a newer release at startup
+    pub no_update_check: bool,
+    /// Keep saves and other player files next to the executable
+    pub portable: bool,
+    /// Hints allowed per game; unlimited if not given
+    pub hint_limit: Option<usize>,
+    /// Large tap targets and swipes from the start, without waiting for
+    /// the first touch
+    pub touch: bool,
+    /// Controller-first profile with large text, as on a TV or Steam Deck
+    pub big_picture: bool,
+    /// No fades, smooth scrolling or flashes
+    pub reduced_motion: bool,
+    /// Most screen flashes a second; never more than the safe ceiling
+    pub max_flash_rate: Option<u32>,
+    /// Maze file or `maze://` link to open instead of the usual maze
+    pub open: Option<String>,
+}
+
+impl LaunchOptions {
+    /// Parses options from the process arguments, ignoring unknown ones
+    pub fn from_args() -> Self {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    fn parse<I: Iterator<Item = String>>(mut args: I) -> Self {
+        let mut options = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--kiosk" => options.kiosk = true,
+                "--attract-after" => {
+                    options.attract_after = parse_number(&arg, args.next()).map(Duration::from_secs)
+                }
+                "--break-after" => {
+                    options.break_after =
+                        parse_number(&arg, args.next()).map(|mins| Duration::from_secs(mins * 60))
+                }
+                "--scan" => {
+                    options.scan_interval =
+                        parse_number(&arg, args.next()).map(Duration::from_millis)
+                }
+                "--two-switch" => options.two_switch = true,
+                "--commentary" => options.commentary = true,
+                "--dev" => options.dev = true,
+                "--portable" => options.portable = true,
+                "--touch" => options.touch = true,
+                "--big-picture" => options.big_picture = true,
+                "--reduced-motion" => options.reduced_motion = true,
+                "--max-flashes" => {
+                    options.max_flash_rate = parse_number(&arg, args.next())
+                        .map(|rate| rate.min(u64::from(u32::MAX)) as u32)
+                }
+                "--no-ghost" => options.no_ghost = true,
+                "--no-update-check" => options.no_update_check = true,
+                "--history" => {
+                    options.history = parse_number(&arg, args.next())
+                        .filter(|&count| count > 0)
+                        .map(|count| count as usize)
+                }
+                "--hints" => {
+                    options.hint_limit = parse_number(&arg, args.next()).map(|count| count as usize)
+                }
+                other if !other.starts_with('-') => options.open = Some(other.to_string()),
+                other => eprintln!("Ignoring unknown argument: {}", other),
+            }
+        }
+        // Visitors must not be able to scrub or jump through play
+        if options.kiosk && options.dev {
+            eprintln!("Ignoring --dev in kiosk mode");
+            options.dev = false;
+        }
+        options
+    }
+}
+
+/// Parses a flag's whole-number value, warning if it is missing or invalid
+fn parse_number(flag: &str, value: Option<String>) -> Option<u64> {
+    match value.as_deref().map(str::parse::<u64>) {
+        Some(Ok(number)) => Some(number),
+        _ => {
+            eprintln!("{} expects a whole number", flag);
+            None
+        }
+    }
+}
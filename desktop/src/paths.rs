@@ -0,0 +1,78 @@
+use std::path::{Path, PathBuf};
+
+use directories::ProjectDirs;
+
+/// Files the game writes for the player, which older versions kept next
+/// to the executable
+const PLAYER_FILES: &[&str] = &[
+    "autosave.json",
+    "ratings.jsonl",
+    "ghosts.json",
+    "screenshots",
+];
+
+/// Where the game reads its content and keeps the player's files.
+pub struct AppDirs {
+    /// Folder holding the executable and the maze that ships with it
+    pub exe: PathBuf,
+    /// Saves, ratings, ghosts and screenshots
+    pub data: PathBuf,
+}
+
+impl AppDirs {
+    /// Finds the folders for this platform. In portable mode everything
+    /// stays next to the executable, for playing from a USB stick.
+    pub fn locate(portable: bool) -> Self {
+        let exe = std::env::current_exe()
+            .ok()
+            .and_then(|path| path.parent().map(|p| p.to_owned()))
+            .unwrap_or_default();
+
+        let data = match ProjectDirs::from("io.github", "gordon-david", "Maze Game") {
+            Some(dirs) if !portable => dirs.data_dir().to_owned(),
+            _ => exe.clone(),
+        };
+        if let Err(e) = std::fs::create_dir_all(&data) {
+            eprintln!("Could not create {}: {}", data.display(), e);
+        }
+
+        let dirs = Self { exe, data };
+        if dirs.data != dirs.exe {
+            dirs.migrate();
+        }
+        dirs
+    }
+
+    /// Moves player files left next to the executable by older versions
+    /// into the data folder, unless the data folder already has them
+    fn migrate(&self) {
+        for name in PLAYER_FILES {
+            let (from, to) = (self.exe.join(name), self.data.join(name));
+            if !from.exists() || to.exists() {
+                continue;
+            }
+            match move_path(&from, &to) {
+                Ok(()) => eprintln!("Moved {} to {}", from.display(), to.display()),
+                Err(e) => eprintln!("Could not move {}: {}", from.display(), e),
+            }
+        }
+    }
+}
+
+/// Renames, or copies and deletes when the data folder is on another drive
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            move_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        std::fs::remove_dir(from)
+    } else {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+/// Switch-access scanning: the choices on screen are highlighted one at a
+/// time and a single key picks the highlighted one.
+///
+/// With one switch the highlight advances on a timer and Space or Enter
+/// selects. With two switches Space advances and Enter selects.
+pub struct Scanner {
+    /// Auto-advance interval; `None` for two-switch mode
+    interval: Option<Duration>,
+    highlighted: usize,
+    last_advance: Instant,
+    /// Room the highlight belongs to, so it resets on entering a new room
+    room: String,
+}
+
+impl Scanner {
+    pub fn one_switch(interval: Duration) -> Self {
+        Self::new(Some(interval))
+    }
+
+    pub fn two_switch() -> Self {
+        Self::new(None)
+    }
+
+    fn new(interval: Option<Duration>) -> Self {
+        Self {
+            interval,
+            highlighted: 0,
+            last_advance: Instant::now(),
+            room: String::new(),
+        }
+    }
+
+    /// Index of the highlighted choice
+    pub fn highlighted(&self) -> usize {
+        self.highlighted
+    }
+
+    /// Advance the highlight and check the switches.
+    /// Returns the index of the choice selected this frame, if any.
+    pub fn update(&mut self, ctx: &egui::Context, room: &str, choices: usize) -> Option<usize> {
+        if self.room != room {
+            self.room = room.to_owned();
+            self.restart();
+        }
+        if choices == 0 {
+            return None;
+        }
+        self.highlighted %= choices;
+
+        let (space, enter) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Space),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            )
+        });
+
+        match self.interval {
+            Some(interval) => {
+                if space || enter {
+                    let selected = self.highlighted;
+                    self.restart();
+                    return Some(selected);
+                }
+                let elapsed = self.last_advance.elapsed();
+                if elapsed >= interval {
+                    self.advance(choices);
+                    ctx.request_repaint_after(interval);
+                } else {
+                    ctx.request_repaint_after(interval - elapsed);
+                }
+            }
+            None => {
+                if enter {
+                    let selected = self.highlighted;
+                    self.restart();
+                    return Some(selected);
+                }
+                if space {
+                    self.advance(choices);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn advance(&mut self, choices: usize) {
+        self.highlighted = (self.highlighted + 1) % choices;
+        self.last_advance = Instant::now();
+    }
+
+    fn restart(&mut self) {
+        self.highlighted = 0;
+        self.last_advance = Instant::now();
+    }
+}
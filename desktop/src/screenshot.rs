@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use eframe::egui;
+use game_core::locale::{self, Locale};
+
+/// How long the "saved" notice stays on screen
+const NOTICE_DURATION: Duration = Duration::from_secs(3);
+
+/// Captures the window to PNG files when F2 is pressed.
+pub struct Screenshots {
+    dir: PathBuf,
+    /// Result of the last capture and when it happened
+    notice: Option<(String, Instant)>,
+}
+
+impl Screenshots {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, notice: None }
+    }
+
+    /// Handle the hotkey and save any captured frames.
+    /// `annotation` lines are drawn onto the frame being captured, dated
+    /// as `locale` writes dates.
+    pub fn update(&mut self, ctx: &egui::Context, annotation: &[String], locale: &Locale) {
+        if ctx.input(|i| i.key_pressed(egui::Key::F2)) {
+            let now = unix_now();
+            let mut lines = annotation.to_vec();
+            lines.push(format!("{} {} UTC", locale.date(now), utc_time(now)));
+            paint_overlay(ctx, &lines.join("\n"));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(Default::default()));
+        }
+
+        let images: Vec<_> = ctx.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                    _ => None,
+                })
+                .collect()
+        });
+        for image in images {
+            let message = match self.save(&image) {
+                Ok(path) => format!("📷 Saved {}", path.display()),
+                Err(e) => format!("Screenshot failed: {}", e),
+            };
+            self.notice = Some((message, Instant::now()));
+        }
+
+        self.show_notice(ctx);
+    }
+
+    fn save(&self, image: &egui::ColorImage) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(&self.dir)?;
+        let name = utc_timestamp(unix_now()).replace([' ', ':'], "-");
+        let path = self
+            .dir
+            .join(format!("{}.png", name.trim_end_matches("-UTC")));
+
+        let [width, height] = image.size;
+        image::save_buffer(
+            &path,
+            image.as_raw(),
+            width as u32,
+            height as u32,
+            image::ColorType::Rgba8,
+        )?;
+        Ok(path)
+    }
+
+    fn show_notice(&mut self, ctx: &egui::Context) {
+        let Some((message, shown_at)) = &self.notice else {
+            return;
+        };
+        let elapsed = shown_at.elapsed();
+        if elapsed >= NOTICE_DURATION {
+            self.notice = None;
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("screenshot_notice"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| ui.label(message.as_str()));
+            });
+        ctx.request_repaint_after(NOTICE_DURATION - elapsed);
+    }
+}
+
+/// Draw the annotation box over everything else in this frame
+fn paint_overlay(ctx: &egui::Context, text: &str) {
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Tooltip,
+        egui::Id::new("screenshot_overlay"),
+    ));
+    let galley = painter.layout_no_wrap(
+        text.to_owned(),
+        egui::FontId::monospace(14.0),
+        egui::Color32::WHITE,
+    );
+
+    let margin = egui::vec2(8.0, 6.0);
+    let bottom_left = ctx.content_rect().left_bottom() + egui::vec2(10.0, -10.0);
+    let rect = egui::Rect::from_min_size(
+        bottom_left - egui::vec2(0.0, galley.size().y + 2.0 * margin.y),
+        galley.size() + 2.0 * margin,
+    );
+
+    painter.rect_filled(rect, 4.0, egui::Color32::from_black_alpha(180));
+    painter.galley(rect.min + margin, galley, egui::Color32::WHITE);
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Formats unix seconds as `YYYY-MM-DD HH:MM:SS UTC`, the same in every
+/// locale so screenshot files sort by date
+fn utc_timestamp(secs: u64) -> String {
+    let (year, month, day) = locale::civil_date(secs);
+    format!("{:04}-{:02}-{:02} {} UTC", year, month, day, utc_time(secs))
+}
+
+/// Formats the time of day of unix seconds as `HH:MM:SS`
+fn utc_time(secs: u64) -> String {
+    let time = secs % 86_400;
+    format!("{:02}:{:02}:{:02}", time / 3600, time / 60 % 60, time % 60)
+}
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use eframe::egui;
+use game_core::locale::Locale;
+use game_core::storage::Storage;
+use serde::{Deserialize, Serialize};
+
+/// Where the settings are kept
+const KEY: &str = "settings.json";
+
+/// Range of the text size slider
+const TEXT_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.75..=2.0;
+
+/// Formats offered besides the system's, by tag and name
+const FORMATS: [(&str, &str); 7] = [
+    ("en-US", "English (US)"),
+    ("en-GB", "English (UK)"),
+    ("de-DE", "German"),
+    ("fr-FR", "French"),
+    ("es-ES", "Spanish"),
+    ("sv-SE", "Swedish"),
+    ("ja-JP", "Japanese"),
+];
+
+/// Light or dark colours, or whichever the system uses.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// The player's preferences, kept between launches. Anything missing
+/// from the saved file, such as a setting added since, takes its default.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub theme: Theme,
+    /// Size of everything on screen, text included
+    pub text_scale: f32,
+    /// Loudness of sounds, from 0 to 1
+    pub volume: f32,
+    /// Number keys pick exits, Enter takes them and R restarts
+    pub hotkeys: bool,
+    /// Locale tag for writing numbers, times and dates; the system's
+    /// if unset
+    pub formats: Option<String>,
+}
+
+/// The settings and the window for changing them.
+pub struct SettingsWindow {
+    pub settings: Settings,
+    /// The settings last put into effect and saved
+    applied: Settings,
+    pub open: bool,
+    storage: Arc<dyn Storage>,
+    /// The locale the system asks for
+    system: Locale,
+    /// The locale in effect, from the settings or the system
+    locale: Locale,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::System,
+            text_scale: 1.0,
+            volume: 0.8,
+            hotkeys: true,
+            formats: None,
+        }
+    }
+}
+
+impl Theme {
+    const ALL: [Theme; 3] = [Self::System, Self::Light, Self::Dark];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::System => "Same as system",
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+        }
+    }
+
+    fn preference(self) -> egui::ThemePreference {
+        match self {
+            Self::System => egui::ThemePreference::System,
+            Self::Light => egui::ThemePreference::Light,
+            Self::Dark => egui::ThemePreference::Dark,
+        }
+    }
+}
+
+impl SettingsWindow {
+    /// Reads the saved settings, or starts from the defaults, and puts
+    /// them into effect
+    pub fn load(storage: Arc<dyn Storage>, ctx: &egui::Context) -> Self {
+        let settings = match storage.read(KEY) {
+            Ok(Some(text)) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                eprintln!("Could not read settings: {}", e);
+                Settings::default()
+            }),
+            Ok(None) => Settings::default(),
+            Err(e) => {
+                eprintln!("Could not read settings: {}", e);
+                Settings::default()
+            }
+        };
+        apply(&settings, ctx);
+        let system = Locale::from_env();
+        Self {
+            locale: chosen_locale(&settings, &system),
+            applied: settings.clone(),
+            settings,
+            open: false,
+            storage,
+            system,
+        }
+    }
+
+    /// How numbers, times and dates are written
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    /// Let widgets know the settings, and show the window while open.
+    /// Changes take effect and are saved as soon as the mouse is let go.
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let hotkeys = self.applied.hotkeys;
+        ctx.data_mut(|d| {
+            d.insert_temp(egui::Id::new("hotkeys"), hotkeys);
+            d.insert_temp(egui::Id::new("locale"), self.locale.clone());
+        });
+        if !self.open {
+            return;
+        }
+        let settings = &mut self.settings;
+        egui::Window::new("⚙ Settings")
+            .open(&mut self.open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("settings").num_columns(2).show(ui, |ui| {
+                    ui.label("Theme");
+                    egui::ComboBox::from_id_salt("theme")
+                        .selected_text(settings.theme.name())
+                        .show_ui(ui, |ui| {
+                            for theme in Theme::ALL {
+                                ui.selectable_value(&mut settings.theme, theme, theme.name());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Text size");
+                    let scale = egui::Slider::new(&mut settings.text_scale, TEXT_SCALE_RANGE)
+                        .custom_formatter(|value, _| format!("{:.0}%", value * 100.0));
+                    ui.add(scale);
+                    ui.end_row();
+
+                    ui.label("Volume");
+                    let volume = egui::Slider::new(&mut settings.volume, 0.0..=1.0)
+                        .custom_formatter(|value, _| format!("{:.0}%", value * 100.0));
+                    ui.add(volume);
+                    ui.end_row();
+
+                    ui.label("Hotkeys");
+                    ui.checkbox(&mut settings.hotkeys, "1–9, Enter and R")
+                        .on_hover_text("Number keys pick an exit, Enter takes it, R restarts");
+                    ui.end_row();
+
+                    ui.label("Numbers and dates");
+                    let selected = match &settings.formats {
+                        Some(tag) => format_name(tag),
+                        None => "Same as system",
+                    };
+                    egui::ComboBox::from_id_salt("formats")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut settings.formats, None, "Same as system");
+                            for (tag, name) in FORMATS {
+                                let format = Some(tag.to_string());
+                                ui.selectable_value(&mut settings.formats, format, name);
+                            }
+                        });
+                    ui.end_row();
+                });
+                ui.add_space(6.0);
+                if ui.button("Reset to defaults").clicked() {
+                    *settings = Settings::default();
+                }
+            });
+
+        // Scaling the screen mid-drag would move the slider under the mouse
+        let dragging = ctx.input(|i| i.pointer.any_down());
+        if self.settings != self.applied && !dragging {
+            apply(&self.settings, ctx);
+            self.locale = chosen_locale(&self.settings, &self.system);
+            self.save();
+            self.applied = self.settings.clone();
+        }
+    }
+
+    fn save(&self) {
+        let saved = serde_json::to_string_pretty(&self.settings)
+            .map_err(|e| e.into())
+            .and_then(|text| self.storage.write(KEY, &text));
+        if let Err(e) = saved {
+            eprintln!("Could not save settings: {}", e);
+        }
+    }
+}
+
+/// Whether exits show and answer to number keys
+pub fn hotkeys(ui: &egui::Ui) -> bool {
+    ui.data(|d| d.get_temp(egui::Id::new("hotkeys")).unwrap_or(true))
+}
+
+/// How numbers, times and dates are written, as the settings say
+pub fn locale(ui: &egui::Ui) -> Locale {
+    ui.data(|d| d.get_temp(egui::Id::new("locale")).unwrap_or_default())
+}
+
+fn chosen_locale(settings: &Settings, system: &Locale) -> Locale {
+    let locale = settings.formats.as_deref().and_then(Locale::parse);
+    Locale {
+        // egui's built-in fonts only have digits 0 to 9
+        native_digits: false,
+        ..locale.unwrap_or_else(|| system.clone())
+    }
+}
+
+/// Name of a format offered, or the tag itself if it is not one of them,
+/// as when set by hand in the settings file
+fn format_name(tag: &str) -> &str {
+    FORMATS
+        .iter()
+        .find(|(offered, _)| *offered == tag)
+        .map_or(tag, |(_, name)| name)
+}
+
+fn apply(settings: &Settings, ctx: &egui::Context) {
+    ctx.set_theme(settings.theme.preference());
+    let (smallest, largest) = TEXT_SCALE_RANGE.into_inner();
+    ctx.set_zoom_factor(settings.text_scale.clamp(smallest, largest));
+}
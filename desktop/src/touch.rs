@@ -0,0 +1,121 @@
+use eframe::egui;
+
+use crate::layout::Panel;
+
+/// Finger travel, in points, that counts as a swipe
+const SWIPE_DISTANCE: f32 = 80.0;
+
+/// How much larger text and tap targets get
+const TOUCH_SCALE: f32 = 1.3;
+
+/// A sideways swipe across the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Swipe {
+    Left,
+    Right,
+}
+
+/// Layout for touchscreens: larger tap targets, swipes, and in portrait
+/// one panel at a time under a row of tabs instead of the dock.
+///
+/// Turned on with --touch, or by the first touch on the screen.
+pub struct TouchMode {
+    active: bool,
+    /// Whether the larger style has been applied
+    styled: bool,
+    /// Where the current press started
+    press_origin: Option<egui::Pos2>,
+    /// Panel shown in the portrait layout
+    pub shown: Panel,
+}
+
+impl TouchMode {
+    pub fn new(forced: bool) -> Self {
+        Self {
+            active: forced,
+            styled: false,
+            press_origin: None,
+            shown: Panel::Play,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Whether to show the compact single-panel layout
+    pub fn is_compact(&self, ctx: &egui::Context) -> bool {
+        let screen = ctx.content_rect();
+        self.active && screen.height() > screen.width()
+    }
+
+    /// Switch on at the first touch, restyle once, and look for swipes.
+    /// Returns the swipe finished this frame, if any.
+    pub fn update(&mut self, ctx: &egui::Context) -> Option<Swipe> {
+        if !self.active && ctx.input(|i| i.any_touches()) {
+            self.active = true;
+        }
+        if !self.active {
+            return None;
+        }
+        if !self.styled {
+            ctx.style_mut(enlarge);
+            self.styled = true;
+        }
+
+        let (pressed, released, position) = ctx.input(|i| {
+            (
+                i.pointer.primary_pressed(),
+                i.pointer.primary_released(),
+                i.pointer.interact_pos(),
+            )
+        });
+        if pressed {
+            self.press_origin = position;
+        }
+        if !released {
+            return None;
+        }
+        let delta = position? - self.press_origin.take()?;
+        // Mostly sideways, so scrolling a list is not taken for a swipe
+        if delta.x.abs() < SWIPE_DISTANCE || delta.x.abs() < 2.0 * delta.y.abs() {
+            return None;
+        }
+        Some(if delta.x > 0.0 {
+            Swipe::Right
+        } else {
+            Swipe::Left
+        })
+    }
+
+    /// A row of tabs, one per panel, wide enough for a thumb.
+    /// Returns the panel tapped, if any.
+    pub fn tab_row(&self, ui: &mut egui::Ui) -> Option<Panel> {
+        let mut tapped = None;
+        ui.columns(Panel::ALL.len(), |columns| {
+            for (column, panel) in columns.iter_mut().zip(Panel::ALL) {
+                let tab = egui::Button::selectable(self.shown == panel, panel.title());
+                if column
+                    .add_sized([column.available_width(), 0.0], tab)
+                    .clicked()
+                {
+                    tapped = Some(panel);
+                }
+            }
+        });
+        tapped
+    }
+}
+
+/// Bigger text, buttons and gaps, so each choice is easy to hit
+fn enlarge(style: &mut egui::Style) {
+    for font in style.text_styles.values_mut() {
+        font.size *= TOUCH_SCALE;
+    }
+    let spacing = &mut style.spacing;
+    spacing.interact_size *= TOUCH_SCALE;
+    spacing.interact_size.y = spacing.interact_size.y.max(44.0);
+    spacing.button_padding *= 2.0;
+    spacing.item_spacing *= 1.5;
+    spacing.scroll.bar_width *= 2.0;
+}
@@ -0,0 +1,139 @@
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use eframe::egui;
+
+/// Latest release of the game, from the GitHub API
+const RELEASES_URL: &str = "https://api.github.com/repos/gordon-david/Maze_Game/releases/latest";
+
+/// Changelog lines shown in the notice
+const CHANGELOG_LINES: usize = 8;
+
+/// A release newer than this build.
+struct Release {
+    version: String,
+    /// Start of the release notes
+    changelog: String,
+    /// Page with the downloads
+    url: String,
+}
+
+/// Asks GitHub for the latest release in the background and offers the
+/// download page if it is newer. Any failure is silent: being offline
+/// must not get in the way of playing.
+pub struct UpdateCheck {
+    result: Receiver<Release>,
+    release: Option<Release>,
+}
+
+impl UpdateCheck {
+    pub fn spawn(ctx: &egui::Context) -> Self {
+        let (sender, result) = mpsc::channel();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            if let Some(release) = latest_release()
+                && is_newer(&release.version, env!("CARGO_PKG_VERSION"))
+            {
+                let _ = sender.send(release);
+                ctx.request_repaint();
+            }
+        });
+        Self {
+            result,
+            release: None,
+        }
+    }
+
+    /// Shows the notice once a newer release is known, until dismissed
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if let Ok(release) = self.result.try_recv() {
+            self.release = Some(release);
+        }
+        let Some(release) = &self.release else {
+            return;
+        };
+
+        let mut dismissed = false;
+        egui::Window::new("Update available")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Maze Game {} is out (you have {}).",
+                    release.version,
+                    env!("CARGO_PKG_VERSION")
+                ));
+                if !release.changelog.is_empty() {
+                    ui.add_space(5.0);
+                    ui.weak(&release.changelog);
+                }
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    // Opening the page lets the player pick the right download
+                    // for their platform rather than replacing a running binary
+                    if ui.button("Open download page").clicked() {
+                        ctx.open_url(egui::OpenUrl::new_tab(&release.url));
+                        dismissed = true;
+                    }
+                    if ui.button("Not now").clicked() {
+                        dismissed = true;
+                    }
+                });
+            });
+        if dismissed {
+            self.release = None;
+        }
+    }
+}
+
+fn latest_release() -> Option<Release> {
+    let output = Command::new("curl")
+        .args(["--silent", "--fail", "--location", "--max-time", "10"])
+        .args(["--header", "Accept: application/vnd.github+json"])
+        .args([
+            "--user-agent",
+            concat!("Maze_Game/", env!("CARGO_PKG_VERSION")),
+        ])
+        .arg(RELEASES_URL)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let version = json["tag_name"]
+        .as_str()?
+        .trim_start_matches('v')
+        .to_string();
+    let changelog = json["body"]
+        .as_str()
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(CHANGELOG_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let url = json["html_url"].as_str()?.to_string();
+    Some(Release {
+        version,
+        changelog,
+        url,
+    })
+}
+
+/// Compares dotted version numbers, treating missing or non-numeric
+/// parts as zero
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .split(['.', '-', '+'])
+            .take(3)
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    parts(candidate) > parts(current)
+}
@@ -0,0 +1,101 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::settings;
+
+/// How long an enforced break lasts, and how much idle time counts as one
+const BREAK_LENGTH: Duration = Duration::from_secs(5 * 60);
+
+/// What the app should do after showing the break prompt
+pub enum BreakOutcome {
+    /// Nothing changed, or the player chose to keep playing
+    Continue,
+    /// An enforced break ended; the next player starts fresh
+    Restart,
+}
+
+/// Tracks continuous play time and prompts the player to take a break.
+pub struct BreakReminder {
+    limit: Duration,
+    /// Enforced breaks cannot be dismissed (kiosk mode)
+    locked: bool,
+    play_started: Instant,
+    break_started: Option<Instant>,
+}
+
+impl BreakReminder {
+    pub fn new(limit: Duration, locked: bool) -> Self {
+        Self {
+            limit,
+            locked,
+            play_started: Instant::now(),
+            break_started: None,
+        }
+    }
+
+    /// Whether the break prompt is up, during which the game is paused
+    pub fn is_on_break(&self) -> bool {
+        self.break_started.is_some()
+    }
+
+    /// Check the play timer and show the break prompt when it is due.
+    pub fn show(&mut self, ctx: &egui::Context, last_input: Instant) -> BreakOutcome {
+        let Some(break_started) = self.break_started else {
+            // Walking away for a while counts as a break
+            if last_input.elapsed() >= BREAK_LENGTH {
+                self.play_started = last_input;
+            }
+
+            let played = self.play_started.elapsed();
+            if played >= self.limit {
+                self.break_started = Some(Instant::now());
+            } else {
+                ctx.request_repaint_after(self.limit - played);
+            }
+            return BreakOutcome::Continue;
+        };
+
+        let mut outcome = BreakOutcome::Continue;
+        let minutes = self.limit.as_secs() / 60;
+
+        egui::Modal::new(egui::Id::new("break_reminder")).show(ctx, |ui| {
+            ui.heading("☕ Time for a break");
+            let locale = settings::locale(ui);
+            ui.label(format!(
+                "You've been playing for {} minutes.",
+                locale.number(minutes as i64)
+            ));
+
+            if self.locked {
+                let remaining = BREAK_LENGTH.saturating_sub(break_started.elapsed());
+                ui.label(format!(
+                    "The game continues in {}.",
+                    locale.duration(remaining)
+                ));
+                if remaining.is_zero() {
+                    outcome = BreakOutcome::Restart;
+                }
+            } else {
+                ui.label("Maybe stretch your legs and rest your eyes?");
+                if ui.button("Keep playing").clicked() {
+                    self.finish_break();
+                }
+            }
+        });
+
+        if self.locked {
+            match outcome {
+                BreakOutcome::Restart => self.finish_break(),
+                BreakOutcome::Continue => ctx.request_repaint_after(Duration::from_secs(1)),
+            }
+        }
+
+        outcome
+    }
+
+    fn finish_break(&mut self) {
+        self.break_started = None;
+        self.play_started = Instant::now();
+    }
+}
@@ -0,0 +1,564 @@
+use eframe::egui;
+use game_core::action::Action;
+use game_core::cooldown::ExitClosed;
+use game_core::ending::{Ending, EndingKind};
+use game_core::grid::Direction;
+use game_core::locale::TextDirection;
+use game_core::minimap::Minimap;
+use game_core::score::{BASE_POINTS, Score};
+use game_core::view::{Choice, CombatView, ConversationView, Stats};
+use game_core::{Exit, ExitGroup, ExitIcon, ExitLayout, Room};
+
+use crate::{big_picture, effects, settings};
+
+/// Height of the scrollable exit list
+const SCROLL_HEIGHT: f32 = 320.0;
+
+/// Exit count from which a room gets a filter box
+const HUB_EXIT_COUNT: usize = 8;
+
+/// Width and height of each arm of the D-pad
+const DPAD_ARM: f32 = 76.0;
+
+/// Seconds a pressed arm takes to fade back, so a tap is felt
+const DPAD_FADE: f32 = 0.3;
+
+/// Keys that pick the first nine open exits, in order
+pub const NUMBER_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
+/// Radius of a room's dot on the minimap
+const MINIMAP_ROOM: f32 = 6.0;
+
+/// Tallest the minimap is drawn
+const MINIMAP_HEIGHT: f32 = 240.0;
+
+/// The exits to show in a room: those that can be taken, and those
+/// closed after use.
+pub struct ShownExits<'a> {
+    pub open: &'a [usize],
+    pub closed: &'a [(usize, ExitClosed)],
+}
+
+impl ShownExits<'_> {
+    fn contains(&self, index: usize) -> bool {
+        self.open.contains(&index) || self.closed.iter().any(|&(i, _)| i == index)
+    }
+
+    fn closed(&self, index: usize) -> Option<ExitClosed> {
+        self.closed
+            .iter()
+            .find(|&&(i, _)| i == index)
+            .map(|&(_, closed)| closed)
+    }
+}
+
+/// Lay out the room's exit buttons as the room asks, with grouped exits
+/// in collapsible sections and a filter box for hub rooms. Only the
+/// `exits` shown appear, those closed after use greyed out and the first
+/// nine open ones labelled with the number key that picks them. `hint`
+/// marks the exit the player was told leads towards the end.
+/// Returns the index of the chosen exit, if any.
+pub fn exit_buttons(
+    ui: &mut egui::Ui,
+    room: &Room,
+    exits: &ShownExits,
+    highlight: Option<usize>,
+    hint: Option<usize>,
+) -> Option<usize> {
+    let mut clicked = None;
+    let mut groups = room.exit_groups();
+    for group in &mut groups {
+        group.exits.retain(|(i, _)| exits.contains(*i));
+    }
+    groups.retain(|g| !g.exits.is_empty());
+
+    if is_hub(room) {
+        let (matches, confirmed) = filter_box(ui, room, exits.open);
+        clicked = confirmed;
+        for group in &mut groups {
+            group.exits.retain(|(i, _)| matches.contains(i));
+        }
+        groups.retain(|g| !g.exits.is_empty());
+
+        // Without scanning, point out what Enter would pick
+        if let (None, Some(&best)) = (highlight, matches.first()) {
+            return clicked.or(grouped_buttons(ui, room, &groups, exits, Some(best), hint));
+        }
+    }
+
+    clicked.or(grouped_buttons(ui, room, &groups, exits, highlight, hint))
+}
+
+/// Rooms with enough exits to warrant a filter box
+fn is_hub(room: &Room) -> bool {
+    room.exits.len() >= HUB_EXIT_COUNT || room.exit_layout() == ExitLayout::Scroll
+}
+
+/// Type-to-filter field. Returns the matching exit indices, best first,
+/// and the best match if the player pressed Enter.
+fn filter_box(ui: &mut egui::Ui, room: &Room, open: &[usize]) -> (Vec<usize>, Option<usize>) {
+    // Keep the filter text in egui's memory, one per room
+    let id = egui::Id::new(("exit_filter", &room.id));
+    let mut filter: String = ui.data_mut(|d| d.get_temp(id).unwrap_or_default());
+
+    // With a controller, keys on screen type and the field only shows them
+    let on_screen = big_picture::is_active(ui);
+    let response = ui
+        .horizontal(|ui| {
+            ui.label("🔍");
+            let field = egui::TextEdit::singleline(&mut filter).hint_text("Type to filter exits");
+            ui.add(field.interactive(!on_screen))
+        })
+        .inner;
+    if on_screen {
+        big_picture::keyboard(ui, &mut filter);
+    }
+    let mut matches = room.matching_exits(&filter);
+    matches.retain(|i| open.contains(i));
+
+    let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+    let confirmed = if entered && !filter.trim().is_empty() {
+        matches.first().copied()
+    } else {
+        None
+    };
+
+    ui.data_mut(|d| d.insert_temp(id, filter));
+    (matches, confirmed)
+}
+
+/// Ungrouped exits first, then each group in a collapsible section
+fn grouped_buttons(
+    ui: &mut egui::Ui,
+    room: &Room,
+    groups: &[ExitGroup],
+    exits: &ShownExits,
+    highlight: Option<usize>,
+    hint: Option<usize>,
+) -> Option<usize> {
+    let mut clicked = None;
+
+    for group in groups {
+        match group.name {
+            None => {
+                let layout = room.exit_layout();
+                let buttons = layout_buttons(ui, room, group, exits, layout, highlight, hint);
+                clicked = clicked.or(buttons);
+            }
+            Some(name) => {
+                // Use the room's own layout choice, else one sized to the group
+                let layout = room
+                    .layout
+                    .unwrap_or_else(|| ExitLayout::for_exit_count(group.exits.len()));
+                egui::CollapsingHeader::new(name)
+                    .id_salt(("exit_group", &room.id, name))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let buttons =
+                            layout_buttons(ui, room, group, exits, layout, highlight, hint);
+                        clicked = clicked.or(buttons);
+                    });
+            }
+        }
+    }
+
+    clicked
+}
+
+fn layout_buttons(
+    ui: &mut egui::Ui,
+    room: &Room,
+    group: &ExitGroup,
+    exits: &ShownExits,
+    layout: ExitLayout,
+    highlight: Option<usize>,
+    hint: Option<usize>,
+) -> Option<usize> {
+    let mut clicked = None;
+    let mut button = |ui: &mut egui::Ui, i: usize, exit: &Exit| {
+        let hotkey = settings::hotkeys(ui)
+            .then(|| exit_hotkey(exits.open, i))
+            .flatten();
+        let closed = exits.closed(i);
+        let text = exit_text(exit, hotkey, closed.map(|closed| closed_text(ui, closed)));
+        let mut button = choice_button(ui, text, highlight == Some(i));
+        if hint == Some(i) && highlight != Some(i) {
+            button = button.stroke(egui::Stroke::new(2.0, ui.visuals().hyperlink_color));
+        }
+        let mut response = ui.add_enabled(closed.is_none(), button);
+        if hint == Some(i) {
+            response = response.on_hover_text("💡 This way leads towards the end");
+        }
+        if response.clicked() {
+            clicked = Some(i);
+        }
+    };
+
+    match layout {
+        ExitLayout::Stack => {
+            for &(i, exit) in &group.exits {
+                button(ui, i, exit);
+            }
+        }
+        ExitLayout::TwoColumns => {
+            ui.columns(2, |columns| {
+                for (n, &(i, exit)) in group.exits.iter().enumerate() {
+                    button(&mut columns[n % 2], i, exit);
+                }
+            });
+        }
+        ExitLayout::Wrap => {
+            ui.horizontal_wrapped(|ui| {
+                for &(i, exit) in &group.exits {
+                    button(ui, i, exit);
+                }
+            });
+        }
+        ExitLayout::Scroll => {
+            egui::ScrollArea::vertical()
+                .id_salt(("exit_scroll", &room.id, group.name))
+                .max_height(SCROLL_HEIGHT)
+                .show(ui, |ui| {
+                    for &(i, exit) in &group.exits {
+                        button(ui, i, exit);
+                    }
+                });
+        }
+    }
+
+    clicked
+}
+
+/// A directional pad for rooms whose exits are compass directions, with
+/// `compass` giving the exit for each of [`Direction::ALL`]. A pressed
+/// arm lights up and sinks in, then fades back. Outlines mark the
+/// scanning `highlight` and the `hint` as in [`exit_buttons`].
+/// Returns the index of the chosen exit, if any.
+pub fn dpad(
+    ui: &mut egui::Ui,
+    room: &Room,
+    compass: [Option<usize>; 4],
+    highlight: Option<usize>,
+    hint: Option<usize>,
+) -> Option<usize> {
+    let mut clicked = None;
+    ui.vertical_centered(|ui| {
+        let size = egui::Vec2::splat(DPAD_ARM * 3.0);
+        let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+        let painter = ui.painter();
+        let visuals = ui.visuals().clone();
+        let hub = visuals.widgets.noninteractive.bg_fill;
+        painter.circle_filled(rect.center(), DPAD_ARM * 0.3, hub);
+
+        for (direction, exit) in Direction::ALL.into_iter().zip(compass) {
+            let (dx, dy) = direction.offset();
+            let centre = rect.center() + egui::vec2(dx as f32, dy as f32) * DPAD_ARM;
+            let arm = egui::Rect::from_center_size(centre, egui::Vec2::splat(DPAD_ARM - 6.0));
+            let Some(i) = exit else {
+                // A wall: drawn so the pad keeps its shape
+                painter.rect_filled(arm, 8.0, visuals.faint_bg_color);
+                continue;
+            };
+
+            let id = ui.id().with(("dpad", direction.name()));
+            let response = ui
+                .interact(arm, id, egui::Sense::click())
+                .on_hover_text(&room.exits[i].label);
+            if response.clicked() {
+                clicked = Some(i);
+            }
+            let down = response.is_pointer_button_down_on();
+            let fade = effects::duration(ui, DPAD_FADE);
+            let pressed = ui.ctx().animate_bool_with_time(id, down, fade);
+
+            let idle = if response.hovered() {
+                visuals.widgets.hovered.bg_fill
+            } else {
+                visuals.widgets.inactive.bg_fill
+            };
+            let stroke = if highlight == Some(i) {
+                egui::Stroke::new(3.0, visuals.warn_fg_color)
+            } else if hint == Some(i) {
+                egui::Stroke::new(2.0, visuals.hyperlink_color)
+            } else {
+                visuals.widgets.inactive.bg_stroke
+            };
+            let arm = arm.shrink(4.0 * pressed);
+            let fill = idle.lerp_to_gamma(visuals.selection.bg_fill, pressed);
+            painter.rect(arm, 8.0, fill, stroke, egui::StrokeKind::Inside);
+            painter.text(
+                arm.center(),
+                egui::Align2::CENTER_CENTER,
+                direction_glyph(direction),
+                egui::FontId::proportional(DPAD_ARM * 0.4),
+                visuals.strong_text_color(),
+            );
+        }
+    });
+    clicked
+}
+
+fn direction_glyph(direction: Direction) -> &'static str {
+    match direction {
+        Direction::North => "⬆",
+        Direction::East => "➡",
+        Direction::South => "⬇",
+        Direction::West => "⬅",
+    }
+}
+
+/// A button for an exit or other choice, outlined when highlighted by
+/// switch scanning
+pub fn choice_button<'a>(
+    ui: &egui::Ui,
+    text: impl Into<egui::WidgetText>,
+    highlighted: bool,
+) -> egui::Button<'a> {
+    let button = egui::Button::new(text);
+    if highlighted {
+        button
+            .selected(true)
+            .stroke(egui::Stroke::new(3.0, ui.visuals().warn_fg_color))
+    } else {
+        button
+    }
+}
+
+/// Button text for an exit: its number key, icon, label and how long it
+/// is closed for, in its colour hint
+pub fn exit_text(exit: &Exit, hotkey: Option<usize>, closed: Option<String>) -> egui::RichText {
+    let mut text = String::new();
+    if let Some(key) = hotkey {
+        text.push_str(&format!("{}  ", key));
+    }
+    if let Some(icon) = exit.icon {
+        text.push_str(&format!("{} ", icon_glyph(icon)));
+    }
+    text.push_str(&exit.label);
+    if let Some(closed) = closed {
+        text.push_str(&format!("  {}", closed));
+    }
+    let text = egui::RichText::new(text);
+    match exit.color {
+        Some(c) => text.color(egui::Color32::from_rgb(c.r, c.g, c.b)),
+        None => text,
+    }
+}
+
+/// How long an exit stays closed, such as "⏳ 3 turns"
+fn closed_text(ui: &egui::Ui, closed: ExitClosed) -> String {
+    match closed {
+        ExitClosed::UsedUp => "🚫 used".to_string(),
+        ExitClosed::Turns(1) => "⏳ 1 turn".to_string(),
+        ExitClosed::Turns(turns) => format!("⏳ {} turns", settings::locale(ui).number(turns)),
+    }
+}
+
+/// Number key that picks the exit at `index`: its place among the
+/// `open` exits, for the first nine
+pub fn exit_hotkey(open: &[usize], index: usize) -> Option<usize> {
+    let place = open.iter().position(|&i| i == index)?;
+    (place < NUMBER_KEYS.len()).then_some(place + 1)
+}
+
+/// Glyph from egui's bundled emoji fonts for each built-in icon
+pub fn icon_glyph(icon: ExitIcon) -> &'static str {
+    match icon {
+        ExitIcon::Door => "🚪",
+        ExitIcon::Stairs => "📶",
+        ExitIcon::Up => "⬆",
+        ExitIcon::Down => "⬇",
+        ExitIcon::Left => "⬅",
+        ExitIcon::Right => "➡",
+        ExitIcon::Key => "🔑",
+        ExitIcon::Lock => "🔒",
+        ExitIcon::Danger => "💀",
+        ExitIcon::Secret => "❓",
+        ExitIcon::Water => "💧",
+        ExitIcon::Fire => "🔥",
+        ExitIcon::Forest => "🌲",
+        ExitIcon::Treasure => "💎",
+    }
+}
+
+/// Small button for a choice from the game view, greyed out with the
+/// reason on hover when unavailable. Returns the action if clicked.
+pub fn choice(ui: &mut egui::Ui, choice: &Choice, text: &str) -> Option<Action> {
+    let response = ui
+        .add_enabled(choice.enabled, egui::Button::new(text))
+        .on_hover_text(&choice.label);
+    let response = match choice.reason {
+        Some(reason) => response.on_disabled_hover_text(reason),
+        None => response,
+    };
+    response.clicked().then(|| choice.action.clone())
+}
+
+/// A character's line in a frame, with a button per reply.
+/// Returns the action of the reply clicked, if any.
+pub fn dialogue(ui: &mut egui::Ui, conversation: &ConversationView) -> Option<Action> {
+    egui::Frame::group(ui.style())
+        .show(ui, |ui| {
+            ui.strong(format!("🗣 {}", conversation.npc.name));
+            ui.label(format!("“{}”", conversation.text));
+            ui.add_space(6.0);
+            let mut clicked = None;
+            for reply in &conversation.replies {
+                clicked = choice(ui, reply, &reply.label).or(clicked);
+            }
+            clicked
+        })
+        .inner
+}
+
+/// The enemy and the player's hit points as bars, with a button per
+/// move. Returns the action of the move clicked, if any.
+pub fn fight(
+    ui: &mut egui::Ui,
+    combat: &CombatView,
+    stats: Stats,
+    highlight: Option<usize>,
+) -> Option<Action> {
+    egui::Frame::group(ui.style())
+        .show(ui, |ui| {
+            let enemy = combat.enemy;
+            let label = ui.strong(format!("⚔ {} attacks!", enemy.name));
+            if let Some(description) = &enemy.description {
+                label.on_hover_text(description);
+            }
+            health_bar(ui, &enemy.name, combat.enemy_hp, enemy.hp);
+            health_bar(ui, "You", stats.health, stats.max_health);
+            ui.add_space(6.0);
+            let mut clicked = None;
+            ui.horizontal(|ui| {
+                for (i, choice) in combat.choices.iter().enumerate() {
+                    let button = choice_button(ui, &choice.label, highlight == Some(i));
+                    if ui.add(button).clicked() {
+                        clicked = Some(choice.action.clone());
+                    }
+                }
+            });
+            clicked
+        })
+        .inner
+}
+
+/// Hit points left out of the most there can be, as a bar
+fn health_bar(ui: &mut egui::Ui, who: &str, hp: u32, max: u32) {
+    let fraction = hp as f32 / max.max(1) as f32;
+    let locale = settings::locale(ui);
+    let text = format!("{}: {} / {} HP", who, locale.number(hp), locale.number(max));
+    ui.add(egui::ProgressBar::new(fraction).text(text));
+}
+
+/// How the final score was reached, line by line
+pub fn score(ui: &mut egui::Ui, score: &Score) {
+    let locale = settings::locale(ui);
+    let number = |value: u32| locale.number(value);
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        egui::Grid::new("score").num_columns(2).show(ui, |ui| {
+            ui.label("Steps");
+            ui.label(format!(
+                "{} (par {})",
+                number(score.steps),
+                number(score.par)
+            ));
+            ui.end_row();
+            ui.label("Extra steps");
+            ui.label(format!("−{}", number(score.step_penalty)));
+            ui.end_row();
+            ui.label(format!("Hints ({})", number(score.hints)));
+            ui.label(format!("−{}", number(score.hint_penalty)));
+            ui.end_row();
+            ui.strong("Score");
+            ui.strong(format!(
+                "{} of {}",
+                number(score.points),
+                number(BASE_POINTS)
+            ));
+            ui.end_row();
+        });
+    });
+}
+
+/// The ending's title, marked with its kind, and its epilogue
+pub fn ending(ui: &mut egui::Ui, ending: &Ending) {
+    ui.heading(format!("{} {}", ending_glyph(ending.kind), ending.title));
+    match &ending.epilogue {
+        Some(epilogue) => ui.label(epilogue),
+        None => ui.label("You reached the end of the maze!"),
+    };
+}
+
+/// Emoji marking each kind of ending
+pub fn ending_glyph(kind: EndingKind) -> &'static str {
+    match kind {
+        EndingKind::Good => "🎉",
+        EndingKind::Secret => "🔮",
+        EndingKind::Bad => "🥀",
+    }
+}
+
+/// Lays out the maze's own text, such as descriptions and exits, from
+/// the right edge if it is written right to left.
+///
+/// egui neither reorders nor joins right-to-left letters, so this only
+/// aligns such text; scripts that need either may not read correctly.
+pub fn maze_text<R>(
+    ui: &mut egui::Ui,
+    direction: TextDirection,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> R {
+    match direction {
+        TextDirection::LeftToRight => add_contents(ui),
+        TextDirection::RightToLeft => {
+            let layout = egui::Layout::top_down(egui::Align::Max);
+            ui.with_layout(layout, add_contents).inner
+        }
+    }
+}
+
+/// Draw the visited rooms as dots joined by their exits, filling the
+/// panel's width, with the current room picked out
+pub fn minimap(ui: &mut egui::Ui, map: &Minimap) {
+    let width = ui.available_width().max(MINIMAP_ROOM * 4.0);
+    let size = egui::vec2(width, width.min(MINIMAP_HEIGHT));
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let inner = rect.shrink(MINIMAP_ROOM * 2.0);
+    let at = |x: f32, y: f32| inner.min + egui::vec2(x * inner.width(), y * inner.height());
+
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    painter.rect_filled(rect, 4.0, visuals.extreme_bg_color);
+    let line = egui::Stroke::new(2.0, visuals.widgets.noninteractive.bg_stroke.color);
+    for &(a, b) in &map.links {
+        let (a, b) = (&map.rooms[a], &map.rooms[b]);
+        painter.line_segment([at(a.x, a.y), at(b.x, b.y)], line);
+    }
+    for room in &map.rooms {
+        let centre = at(room.x, room.y);
+        let (radius, fill) = if room.current {
+            (MINIMAP_ROOM * 1.4, visuals.selection.bg_fill)
+        } else {
+            (MINIMAP_ROOM, visuals.widgets.inactive.fg_stroke.color)
+        };
+        painter.circle_filled(centre, radius, fill);
+        let spot = egui::Rect::from_center_size(centre, egui::Vec2::splat(radius * 2.0));
+        ui.interact(
+            spot,
+            ui.id().with(("minimap", room.id)),
+            egui::Sense::hover(),
+        )
+        .on_hover_text(room.id);
+    }
+}
@@ -0,0 +1,125 @@
+//! Achievements a player can earn, and the record of those earned.
+//!
+//! Each achievement is judged from a game's state, so any frontend can
+//! ask a game which it has earned. An [`AchievementLog`] keeps the ones a
+//! player has unlocked across games and mazes, in a file of its own.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::ending::EndingKind;
+use crate::storage::Storage;
+
+/// Something worth doing in a maze.
+pub struct Achievement {
+    pub id: &'static str,
+    pub title: &'static str,
+    /// How to earn it
+    pub description: &'static str,
+    earned: fn(&GameState) -> bool,
+}
+
+/// Every achievement there is, in the order they are listed
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "finished",
+        title: "Way out",
+        description: "Finish a maze.",
+        earned: |game| game.ending().is_some(),
+    },
+    Achievement {
+        id: "no_hints",
+        title: "Finished without hints",
+        description: "Finish a maze without asking for a hint.",
+        earned: |game| game.ending().is_some() && game.play.hints_used == 0,
+    },
+    Achievement {
+        id: "secret",
+        title: "Found the secret room",
+        description: "Reach a secret ending.",
+        earned: |game| {
+            game.ending()
+                .is_some_and(|ending| ending.kind == EndingKind::Secret)
+        },
+    },
+    Achievement {
+        id: "speed_demon",
+        title: "Speed demon",
+        description: "Finish a maze in the fewest steps it can be done in.",
+        earned: |game| game.score().is_some_and(|score| score.steps <= score.par),
+    },
+    Achievement {
+        id: "explorer",
+        title: "Explorer",
+        description: "Visit every room of a maze.",
+        earned: |game| game.play.visited.len() == game.maze.rooms.len(),
+    },
+    Achievement {
+        id: "victor",
+        title: "Victor",
+        description: "Beat an enemy in a fight.",
+        earned: |game| !game.play.defeated.is_empty(),
+    },
+];
+
+/// Achievements a player has unlocked, by id, each with the maze it was
+/// first earned in.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AchievementLog {
+    unlocked: BTreeMap<String, String>,
+}
+
+impl Achievement {
+    pub fn by_id(id: &str) -> Option<&'static Achievement> {
+        ACHIEVEMENTS.iter().find(|achievement| achievement.id == id)
+    }
+}
+
+impl GameState {
+    /// Achievements this game has earned so far
+    pub fn achievements_earned(&self) -> impl Iterator<Item = &'static Achievement> + '_ {
+        ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| (achievement.earned)(self))
+    }
+}
+
+impl AchievementLog {
+    /// Reads the log under `key`, which is empty if there is none yet
+    pub fn load(storage: &dyn Storage, key: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        match storage.read(key)? {
+            Some(text) => Ok(serde_json::from_str(&text)?),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self, storage: &dyn Storage, key: &str) -> Result<(), Box<dyn std::error::Error>> {
+        storage.write(key, &serde_json::to_string_pretty(self)?)
+    }
+
+    /// Unlocks whatever `game`, played on `maze`, has earned that was not
+    /// unlocked before. Returns the newly unlocked ones.
+    pub fn record(&mut self, game: &GameState, maze: &str) -> Vec<&'static Achievement> {
+        game.achievements_earned()
+            .filter(|achievement| {
+                let new = !self.unlocked.contains_key(achievement.id);
+                if new {
+                    self.unlocked
+                        .insert(achievement.id.to_string(), maze.to_string());
+                }
+                new
+            })
+            .collect()
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains_key(id)
+    }
+
+    /// The maze an achievement was first earned in, if unlocked
+    pub fn earned_in(&self, id: &str) -> Option<&str> {
+        self.unlocked.get(id).map(String::as_str)
+    }
+}
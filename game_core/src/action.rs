@@ -0,0 +1,191 @@
+//! Player actions as values, so frontends, replays and scripts can pass
+//! them around and apply them the same way.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::event::{Before, GameEvent};
+
+/// Something the player can do.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Take the exit at this index in the current room
+    ChooseExit(usize),
+    /// Pick up an item in the current room
+    TakeItem(String),
+    /// Put a carried item down in the current room
+    DropItem(String),
+    /// Add or remove a bookmark on a visited room
+    ToggleBookmark(String),
+    /// Fast-travel to a visited room
+    TravelTo(String),
+    /// Explore the finished maze freely
+    StartFreeRoam,
+    /// Jump to any room during free roam
+    RoamTo(String),
+    /// Start talking to a character in the current room
+    Talk(String),
+    /// Give the reply at this index in the conversation
+    Reply(usize),
+    /// Walk away from the conversation
+    EndConversation,
+    /// Move the cutscene on to its next beat
+    NextBeat,
+    /// Skip the rest of the cutscene
+    SkipCutscene,
+    /// Strike the enemy being fought
+    Attack,
+    /// Run from the fight back to the previous room
+    Flee,
+    /// Spend a reroll token to draw the last random exit again
+    Reroll,
+}
+
+/// The action that stopped a batch, and its position in the batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejected {
+    pub index: usize,
+    pub action: Action,
+}
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "action {} ({:?}) is not allowed",
+            self.index + 1,
+            self.action
+        )
+    }
+}
+
+impl std::error::Error for Rejected {}
+
+impl GameState {
+    /// Applies actions in order as one step: if any is not allowed, the
+    /// game is left as it was. Returns the events of the whole batch,
+    /// which undoes as a single action, and queues them too.
+    pub fn apply_all(&mut self, actions: &[Action]) -> Result<Vec<GameEvent>, Rejected> {
+        let start = self.play.clone();
+        // Script output and events the batch queues go if it is rejected
+        let (messages, queued) = (self.messages.len(), self.events.len());
+
+        for (index, action) in actions.iter().enumerate() {
+            let before = Before::capture(&self.play);
+            if !self.perform(action) {
+                self.play = start;
+                self.messages.truncate(messages);
+                self.events.truncate(queued);
+                return Err(Rejected {
+                    index,
+                    action: action.clone(),
+                });
+            }
+            let events = before.events(&self.play);
+            self.events.extend(events);
+        }
+
+        if !actions.is_empty() {
+            self.undo.push(start);
+        }
+        Ok(self.events[queued..].to_vec())
+    }
+
+    /// Applies an action and queues the events it caused. Returns false
+    /// if it was not allowed, in which case nothing changed.
+    pub fn apply(&mut self, action: &Action) -> bool {
+        let before = self.play.clone();
+        let changes = Before::capture(&self.play);
+        let applied = self.perform(action);
+        if applied {
+            let events = changes.events(&self.play);
+            self.events.extend(events);
+            self.undo.push(before);
+        }
+        applied
+    }
+
+    /// Applies an action without recording it for undo
+    pub(crate) fn perform(&mut self, action: &Action) -> bool {
+        // A reroll is only on offer straight after the draw it redoes
+        let redraw = self.play.redraw.take();
+        let applied = match action {
+            Action::ChooseExit(index) => {
+                let can_move = self.can_act();
+                let available = self.exit_available(*index);
+                if can_move && !available && *index < self.current_room().exits.len() {
+                    self.events.push(GameEvent::ExitBlocked(*index));
+                }
+                if can_move && available {
+                    self.choose_exit(*index);
+                }
+                can_move && available
+            }
+            Action::TakeItem(id) => self.take_item(id),
+            Action::DropItem(id) => self.drop_item(id),
+            Action::ToggleBookmark(id) => {
+                let allowed = self.has_visited(id) || self.is_bookmarked(id);
+                if allowed {
+                    self.toggle_bookmark(id);
+                }
+                allowed
+            }
+            Action::TravelTo(id) => self.travel_to_visited(id).is_ok(),
+            Action::StartFreeRoam => !self.is_roaming() && self.start_free_roam(),
+            Action::RoamTo(id) => self.roam_to(id),
+            Action::Talk(id) => self.talk_to(id),
+            Action::Reply(index) => self.reply(*index),
+            Action::EndConversation => self.end_conversation(),
+            Action::NextBeat => self.next_beat(),
+            Action::SkipCutscene => self.skip_cutscene(),
+            Action::Attack => self.attack(),
+            Action::Flee => self.flee(),
+            Action::Reroll => redraw.clone().is_some_and(|redraw| self.reroll(redraw)),
+        };
+        if !applied {
+            self.play.redraw = redraw;
+        }
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+
+    #[test]
+    fn apply_all_leaves_the_game_as_it_was_when_rejected() {
+        let file: MazeFile = serde_json::from_str(
+            r#"{"rooms": [
+                {"id": "a", "description": "A", "exits": [
+                    {"label": "Slide", "destination": "b",
+                     "hazard": {"cause": "the slide", "damage": 1, "message": "Ouch"}}
+                ]},
+                {"id": "b", "description": "B", "exits": [
+                    {"label": "Locked", "destination": "c", "when": "key"}
+                ]},
+                {"id": "c", "description": "C", "exits": [], "is_end": true}
+            ]}"#,
+        )
+        .unwrap();
+        let mut state = GameState::from_maze(file).unwrap();
+        state.messages.push("Earlier".to_string());
+        state.events.push(GameEvent::ExitBlocked(7));
+        let play = serde_json::to_value(&state.play).unwrap();
+        let (messages, events) = (state.messages.clone(), state.events.clone());
+
+        let rejected = state
+            .apply_all(&[Action::ChooseExit(0), Action::ChooseExit(0)])
+            .unwrap_err();
+
+        assert_eq!(rejected.index, 1);
+        assert_eq!(serde_json::to_value(&state.play).unwrap(), play);
+        assert_eq!(state.messages, messages);
+        assert_eq!(state.events, events);
+        assert!(!state.undo());
+    }
+}
@@ -0,0 +1,123 @@
+//! Ambient sound layers chosen by room tags.
+//!
+//! A maze maps tags such as "water" or "wind" to looping sounds. Each
+//! tag on the current room plays its layer at full volume, and tags on
+//! rooms one exit away play quietly, so a stream is heard before it is
+//! reached. A [`Mixer`] fades layers towards these levels over time, so
+//! walking between areas crossfades instead of switching tracks.
+//!
+//! Core only works out the levels; frontends play the sounds.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+
+/// Share of a layer's volume heard from a neighbouring room
+pub const NEIGHBOUR_LEVEL: f32 = 0.25;
+
+/// How long a layer takes to fade fully in or out unless set otherwise
+pub const DEFAULT_FADE: Duration = Duration::from_secs(2);
+
+/// A looping sound played where its tag is.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AmbientLayer {
+    /// Sound file, relative to the maze file
+    pub sound: String,
+    /// Loudest the layer plays, from 0 to 1
+    #[serde(default = "full_volume")]
+    pub volume: f32,
+}
+
+fn full_volume() -> f32 {
+    1.0
+}
+
+/// Fades ambient layers towards the levels the current room calls for.
+#[derive(Debug, Clone)]
+pub struct Mixer {
+    /// Current level of each sounding layer, by tag
+    levels: BTreeMap<String, f32>,
+    fade: Duration,
+}
+
+impl GameState {
+    /// The level each layer should play at in the current room, by tag.
+    /// Layers not listed should be silent.
+    pub fn ambient_levels(&self) -> BTreeMap<&str, f32> {
+        let mut levels = BTreeMap::new();
+        let room = self.current_room();
+        let neighbours = room
+            .exits
+            .iter()
+            .filter(|exit| exit.is_open(&self.play.flags))
+            .filter_map(|exit| self.get_room(&exit.destination));
+        for (place, share) in neighbours
+            .map(|room| (room, NEIGHBOUR_LEVEL))
+            .chain([(room, 1.0)])
+        {
+            for tag in &place.tags {
+                let Some(layer) = self.maze.ambience.get(tag) else {
+                    continue;
+                };
+                let level = levels.entry(tag.as_str()).or_insert(0.0f32);
+                *level = level.max(layer.volume.clamp(0.0, 1.0) * share);
+            }
+        }
+        levels
+    }
+}
+
+impl Mixer {
+    /// A mixer taking `fade` to bring a layer fully in or out
+    pub fn new(fade: Duration) -> Self {
+        Self {
+            levels: BTreeMap::new(),
+            fade,
+        }
+    }
+
+    /// Moves each layer towards its level in `targets` by the share of
+    /// the fade `elapsed` covers. Layers that fade out are dropped.
+    pub fn update(&mut self, targets: &BTreeMap<&str, f32>, elapsed: Duration) {
+        let step = if self.fade.is_zero() {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / self.fade.as_secs_f32()
+        };
+        for (&tag, _) in targets.iter().filter(|(_, level)| **level > 0.0) {
+            self.levels.entry(tag.to_string()).or_insert(0.0);
+        }
+        self.levels.retain(|tag, level| {
+            let target = targets.get(tag.as_str()).copied().unwrap_or(0.0);
+            *level = if *level < target {
+                (*level + step).min(target)
+            } else {
+                (*level - step).max(target)
+            };
+            *level > 0.0 || target > 0.0
+        });
+    }
+
+    /// Layers sounding now and their levels, by tag
+    pub fn levels(&self) -> &BTreeMap<String, f32> {
+        &self.levels
+    }
+
+    /// Whether layers are still fading, so a frontend keeps updating
+    pub fn is_fading(&self, targets: &BTreeMap<&str, f32>) -> bool {
+        self.levels.len() != targets.len()
+            || self
+                .levels
+                .iter()
+                .any(|(tag, level)| targets.get(tag.as_str()) != Some(level))
+    }
+}
+
+impl Default for Mixer {
+    fn default() -> Self {
+        Self::new(DEFAULT_FADE)
+    }
+}
@@ -0,0 +1,58 @@
+//! Structural and simulation-based maze analysis.
+
+use crate::GameState;
+use crate::simulate::{self, RandomWalk, SimulationConfig};
+
+/// Number of random walks used for the difficulty estimate
+const DIFFICULTY_RUNS: usize = 500;
+
+/// Moves after which a random walk is counted as lost
+const DIFFICULTY_MAX_MOVES: usize = 2000;
+
+/// Summary of a maze's shape and how hard it is to finish.
+#[derive(Clone, Debug)]
+pub struct MazeAnalysis {
+    pub room_count: usize,
+    pub exit_count: usize,
+    /// Rooms without exits that are not end rooms
+    pub dead_ends: usize,
+    pub end_rooms: usize,
+    pub difficulty: Difficulty,
+}
+
+/// Difficulty estimated by simulating random walkers.
+#[derive(Clone, Debug)]
+pub struct Difficulty {
+    /// Average moves a random walker needs to reach an end room
+    pub expected_moves: Option<f64>,
+    /// Fraction of random walkers that reach an end room at all
+    pub completion_rate: f64,
+}
+
+impl GameState {
+    /// Analyses the maze from the current room
+    pub fn analysis(&self) -> MazeAnalysis {
+        let config = SimulationConfig {
+            runs: DIFFICULTY_RUNS,
+            max_moves: DIFFICULTY_MAX_MOVES,
+            seed: 0,
+        };
+        let report = simulate::run_batch(self, &mut RandomWalk, &config);
+
+        MazeAnalysis {
+            room_count: self.maze.rooms.len(),
+            exit_count: self.maze.rooms.iter().map(|r| r.exits.len()).sum(),
+            dead_ends: self
+                .maze
+                .rooms
+                .iter()
+                .filter(|r| r.exits.is_empty() && !r.is_end)
+                .count(),
+            end_rooms: self.maze.rooms.iter().filter(|r| r.is_end).count(),
+            difficulty: Difficulty {
+                expected_moves: report.average_moves(),
+                completion_rate: report.completion_rate(),
+            },
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! The rooms a player has discovered, across every maze they play.
+//!
+//! Each distinct room found becomes a card: a short excerpt of its
+//! description, the maze it came from and how often it was visited. A
+//! card's rarity comes from those visits, against the other rooms found
+//! in the same maze, so the places players seldom pass are the prized
+//! ones. Like the [`AchievementLog`](crate::achievements::AchievementLog),
+//! the collection is kept in a file of its own.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::storage::Storage;
+
+/// Longest excerpt kept on a card, in characters
+pub const EXCERPT_LENGTH: usize = 80;
+
+/// A discovered room.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Card {
+    /// Start of the room's description when it was found
+    pub excerpt: String,
+    /// Times the player has entered the room
+    pub visits: u32,
+}
+
+/// How prized a card is, from how seldom its room is visited.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+/// Every card found, by maze and then by room id.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Collection {
+    mazes: BTreeMap<String, BTreeMap<String, Card>>,
+}
+
+impl Rarity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Common => "Common",
+            Self::Uncommon => "Uncommon",
+            Self::Rare => "Rare",
+        }
+    }
+}
+
+impl Collection {
+    /// Reads the collection under `key`, which is empty if there is none yet
+    pub fn load(storage: &dyn Storage, key: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        match storage.read(key)? {
+            Some(text) => Ok(serde_json::from_str(&text)?),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self, storage: &dyn Storage, key: &str) -> Result<(), Box<dyn std::error::Error>> {
+        storage.write(key, &serde_json::to_string_pretty(self)?)
+    }
+
+    /// Counts a visit to the room `game` is in, played on `maze`.
+    /// Returns true if the room was not in the collection before.
+    pub fn visit(&mut self, game: &GameState, maze: &str) -> bool {
+        let room = game.current_room();
+        let cards = self.mazes.entry(maze.to_string()).or_default();
+        match cards.get_mut(&room.id) {
+            Some(card) => {
+                card.visits = card.visits.saturating_add(1);
+                false
+            }
+            None => {
+                let card = Card {
+                    excerpt: excerpt(&game.room_description(room)),
+                    visits: 1,
+                };
+                cards.insert(room.id.clone(), card);
+                true
+            }
+        }
+    }
+
+    /// Cards found in each maze, by room id
+    pub fn mazes(&self) -> impl Iterator<Item = (&str, &BTreeMap<String, Card>)> {
+        self.mazes
+            .iter()
+            .map(|(maze, cards)| (maze.as_str(), cards))
+    }
+
+    /// Number of cards found across all mazes
+    pub fn len(&self) -> usize {
+        self.mazes.values().map(BTreeMap::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// How rare the card for `room` in `maze` is: rooms visited a fifth
+    /// as often as the most visited room in that maze are rare, and a
+    /// half as often uncommon
+    pub fn rarity(&self, maze: &str, room: &str) -> Option<Rarity> {
+        let cards = self.mazes.get(maze)?;
+        let visits = u64::from(cards.get(room)?.visits);
+        let most = cards
+            .values()
+            .map(|card| u64::from(card.visits))
+            .max()
+            .unwrap_or(visits);
+        Some(if visits * 5 <= most {
+            Rarity::Rare
+        } else if visits * 2 <= most {
+            Rarity::Uncommon
+        } else {
+            Rarity::Common
+        })
+    }
+}
+
+/// The first sentence of a description, cut short at a word if long
+fn excerpt(description: &str) -> String {
+    let description = description.trim();
+    let sentence = match description.find(". ") {
+        Some(end) => &description[..=end],
+        None => description,
+    };
+    if sentence.chars().count() <= EXCERPT_LENGTH {
+        return sentence.to_string();
+    }
+    let cut: String = sentence.chars().take(EXCERPT_LENGTH).collect();
+    let words = match cut.rfind(' ') {
+        Some(space) => &cut[..space],
+        None => &cut,
+    };
+    format!("{}…", words.trim_end_matches([',', ';', ':']))
+}
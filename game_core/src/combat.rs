@@ -0,0 +1,157 @@
+//! Turn-based fights with enemies standing in rooms.
+//!
+//! Walking into a room with an enemy that is still standing starts a
+//! fight. Each turn the player attacks or flees. An attack takes the
+//! player's attack off the enemy's hit points and, unless that beats it,
+//! the enemy strikes back. Fleeing goes back to the previous room but
+//! costs one blow. A player with no hit points left has lost the game.
+//!
+//! Fights have no dice, so replays and solvers see the same outcome
+//! every time.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GameState, Phase};
+
+/// The player's hit points, attack, [stamina](crate::hazard),
+/// [luck](crate::luck) and reroll tokens at the start. Fields left out
+/// take their defaults.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct Fighter {
+    pub hp: u32,
+    pub attack: u32,
+    pub stamina: u32,
+    pub luck: u32,
+    pub rerolls: u32,
+}
+
+impl Default for Fighter {
+    fn default() -> Self {
+        Self {
+            hp: 10,
+            attack: 2,
+            stamina: 10,
+            luck: 0,
+            rerolls: 0,
+        }
+    }
+}
+
+/// Something in a room that fights the player on sight.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Enemy {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub hp: u32,
+    pub attack: u32,
+}
+
+/// A fight going on in the current room.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Combat {
+    pub enemy: String,
+    pub enemy_hp: u32,
+    /// Room the player came from, where fleeing leads
+    pub fled_to: String,
+}
+
+/// Hit points a game starts with when the save does not say
+pub(crate) fn default_health() -> u32 {
+    Fighter::default().hp
+}
+
+impl GameState {
+    /// The enemy being fought and its hit points left, if any
+    pub fn combat(&self) -> Option<(&Enemy, u32)> {
+        let combat = self.play.combat.as_ref()?;
+        let enemy = self
+            .current_room()
+            .enemy
+            .as_ref()
+            .filter(|e| e.id == combat.enemy)?;
+        Some((enemy, combat.enemy_hp))
+    }
+
+    pub fn in_combat(&self) -> bool {
+        self.play.combat.is_some()
+    }
+
+    /// Starts a fight if the current room holds an enemy not yet beaten
+    pub(crate) fn meet_enemy(&mut self, from: String) {
+        let Some(enemy) = &self.current_room().enemy else {
+            return;
+        };
+        if self.play.defeated.contains(&enemy.id) {
+            return;
+        }
+        self.play.combat = Some(Combat {
+            enemy: enemy.id.clone(),
+            enemy_hp: enemy.hp,
+            fled_to: from,
+        });
+    }
+
+    /// Strikes the enemy, which strikes back if still standing.
+    /// Returns false if there is no fight.
+    pub fn attack(&mut self) -> bool {
+        let Some((enemy, hp)) = self.combat() else {
+            return false;
+        };
+        if !self.play.phase.can_move() {
+            return false;
+        }
+        let (id, name, blow) = (enemy.id.clone(), enemy.name.clone(), enemy.attack);
+
+        let hp = hp.saturating_sub(self.maze.player.attack);
+        if hp == 0 {
+            self.play.combat = None;
+            self.play.defeated.push(id);
+            return true;
+        }
+        if let Some(combat) = &mut self.play.combat {
+            combat.enemy_hp = hp;
+        }
+        self.take_blow(blow, &name);
+        true
+    }
+
+    /// Runs back to the previous room, taking one blow on the way.
+    /// Returns false if there is no fight.
+    pub fn flee(&mut self) -> bool {
+        let Some((enemy, _)) = self.combat() else {
+            return false;
+        };
+        if !self.play.phase.can_move() {
+            return false;
+        }
+        let (name, blow) = (enemy.name.clone(), enemy.attack);
+        let Some(combat) = self.play.combat.take() else {
+            return false;
+        };
+
+        self.take_blow(blow, &name);
+        if !self.play.is_game_over() {
+            self.move_to(combat.fled_to);
+        }
+        true
+    }
+
+    /// Loses `damage` hit points to `cause`, ending the game at zero
+    pub(crate) fn take_blow(&mut self, damage: u32, cause: &str) {
+        self.play.health = self.play.health.saturating_sub(damage);
+        if self.play.health == 0 {
+            self.lose(cause);
+        }
+    }
+
+    /// Ends the game as lost to `cause`
+    pub(crate) fn lose(&mut self, cause: &str) {
+        self.play.combat = None;
+        self.play.phase = Phase::GameOver {
+            cause: cause.to_string(),
+        };
+    }
+}
@@ -0,0 +1,104 @@
+//! Pluggable filtering of player-visible maze text.
+
+use crate::MazeFile;
+
+/// Words masked by [`WordlistFilter::default`]
+const DEFAULT_WORDLIST: &[&str] = &[
+    "arse", "ass", "asshole", "bastard", "bitch", "bollocks", "crap", "cunt", "damn", "dick",
+    "fuck", "fucking", "piss", "shit", "slut", "twat", "wanker", "whore",
+];
+
+/// Rewrites text before it is shown to the player.
+pub trait ContentFilter {
+    /// Returns the filtered version of `text`
+    fn filter(&self, text: &str) -> String;
+}
+
+/// Masks whole words from a list with asterisks, ignoring case.
+pub struct WordlistFilter {
+    words: Vec<String>,
+}
+
+impl WordlistFilter {
+    /// Creates a filter masking the given words
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            words: words
+                .into_iter()
+                .map(|w| w.as_ref().to_lowercase())
+                .collect(),
+        }
+    }
+
+    fn is_blocked(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        self.words.contains(&word)
+    }
+}
+
+impl Default for WordlistFilter {
+    fn default() -> Self {
+        Self::new(DEFAULT_WORDLIST)
+    }
+}
+
+impl ContentFilter for WordlistFilter {
+    fn filter(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut word = String::new();
+
+        // Flush the pending word, masking it if blocked
+        let flush = |word: &mut String, out: &mut String| {
+            if self.is_blocked(word) {
+                out.extend(word.chars().map(|_| '*'));
+            } else {
+                out.push_str(word);
+            }
+            word.clear();
+        };
+
+        for c in text.chars() {
+            if c.is_alphanumeric() {
+                word.push(c);
+            } else {
+                flush(&mut word, &mut out);
+                out.push(c);
+            }
+        }
+        flush(&mut word, &mut out);
+
+        out
+    }
+}
+
+impl MazeFile {
+    /// Runs every room description, conditional or not, commentary, exit
+    /// label and line of dialogue through `filter`
+    pub fn apply_filter(&mut self, filter: &dyn ContentFilter) {
+        for room in &mut self.rooms {
+            room.description = filter.filter(&room.description);
+            for variant in &mut room.conditional_descriptions {
+                variant.description = filter.filter(&variant.description);
+            }
+            for layer in &mut room.description_layers {
+                layer.text = filter.filter(&layer.text);
+            }
+            if let Some(commentary) = &mut room.commentary {
+                *commentary = filter.filter(commentary);
+            }
+            for exit in &mut room.exits {
+                exit.label = filter.filter(&exit.label);
+            }
+            for line in room.npcs.iter_mut().flat_map(|npc| &mut npc.dialogue) {
+                line.text = filter.filter(&line.text);
+                for reply in &mut line.replies {
+                    reply.label = filter.filter(&reply.label);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Exits that close for a while after each use, or for good after one.
+//!
+//! An exit marked `once` can be taken a single time. One with
+//! `cooldown_turns` stays closed for that many turns after each use, like
+//! a drawbridge raised behind the player. A turn is one exit taken,
+//! anywhere in the maze. Closed exits are still shown, so frontends can
+//! grey them out and say how long is left, see [`GameState::exit_closed`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GameState, Room};
+
+/// Why an exit that is shown cannot be taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitClosed {
+    /// Taken already, and only usable once
+    UsedUp,
+    /// Closed for this many more turns
+    Turns(u32),
+}
+
+/// The turn an exit that closes after use was last taken.
+///
+/// Kept as a list rather than a map by index, since saves flatten the
+/// play state and serde cannot read number keys through a flatten.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExitUse {
+    /// Index of the exit in its room
+    pub exit: usize,
+    pub turn: u32,
+}
+
+impl GameState {
+    /// Whether the current room's exit at `index` is closed after use,
+    /// and for how long
+    pub fn exit_closed(&self, index: usize) -> Option<ExitClosed> {
+        self.exit_closed_in(self.current_room(), index)
+    }
+
+    pub(crate) fn exit_closed_in(&self, room: &Room, index: usize) -> Option<ExitClosed> {
+        let exit = room.exits.get(index)?;
+        let uses = self.play.exit_uses.get(&room.id)?;
+        let used_at = uses.iter().find(|used| used.exit == index)?.turn;
+        if exit.once {
+            return Some(ExitClosed::UsedUp);
+        }
+        let reopens_at = used_at.saturating_add(exit.cooldown_turns?);
+        let left = reopens_at.saturating_sub(self.play.turns);
+        (left > 0).then_some(ExitClosed::Turns(left))
+    }
+
+    /// Notes that the exit at `index` of `room` was just taken
+    pub(crate) fn record_exit_use(&mut self, room: String, index: usize) {
+        let exit = self.get_room(&room).and_then(|room| room.exits.get(index));
+        if exit.is_some_and(|exit| exit.once || exit.cooldown_turns.is_some()) {
+            let turn = self.play.turns;
+            let uses = self.play.exit_uses.entry(room).or_default();
+            match uses.iter_mut().find(|used| used.exit == index) {
+                Some(used) => used.turn = turn,
+                None => uses.push(ExitUse { exit: index, turn }),
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Short authored sequences played on entering key rooms.
+//!
+//! A cutscene is a list of beats: a line of text, optionally with an
+//! image and a sound, and how long to hold it before the next. It plays
+//! the first time the player enters its room, or every time if it says
+//! so. The player can move on a beat early or skip the rest.
+//!
+//! Core only keeps which beat is showing. Frontends keep the time, and
+//! ask for the next beat when its delay is up, so replays stay the same
+//! whatever the clock does.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+
+/// A sequence of beats belonging to a room.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Cutscene {
+    pub beats: Vec<Beat>,
+    /// Play on every visit instead of only the first
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub repeat: bool,
+}
+
+/// One step of a cutscene.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Beat {
+    pub text: String,
+    /// Picture shown with the text, relative to the maze file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Sound played as the beat starts, relative to the maze file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sound: Option<String>,
+    /// How long to hold the beat; without one it waits for the player
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_ms: Option<u64>,
+}
+
+/// The cutscene being played and the beat it is at.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Playback {
+    pub room: String,
+    pub beat: usize,
+}
+
+impl Beat {
+    pub fn delay(&self) -> Option<Duration> {
+        self.delay_ms.map(Duration::from_millis)
+    }
+}
+
+impl GameState {
+    /// The cutscene playing and the index of its current beat, if any
+    pub fn cutscene(&self) -> Option<(&Cutscene, usize)> {
+        let playback = self.play.cutscene.as_ref()?;
+        let cutscene = self.get_room(&playback.room)?.cutscene.as_ref()?;
+        Some((cutscene, playback.beat))
+    }
+
+    pub fn in_cutscene(&self) -> bool {
+        self.play.cutscene.is_some()
+    }
+
+    /// Starts the current room's cutscene, if it has one to play now
+    pub(crate) fn start_cutscene(&mut self, first_visit: bool) {
+        let Some(cutscene) = &self.current_room().cutscene else {
+            return;
+        };
+        if cutscene.beats.is_empty() || !(first_visit || cutscene.repeat) {
+            return;
+        }
+        self.play.cutscene = Some(Playback {
+            room: self.play.current_room.clone(),
+            beat: 0,
+        });
+    }
+
+    /// Moves on to the next beat, ending the cutscene after the last.
+    /// Returns false if none is playing.
+    pub fn next_beat(&mut self) -> bool {
+        let Some((cutscene, beat)) = self.cutscene() else {
+            return false;
+        };
+        if beat + 1 < cutscene.beats.len() {
+            if let Some(playback) = &mut self.play.cutscene {
+                playback.beat += 1;
+            }
+        } else {
+            self.play.cutscene = None;
+        }
+        true
+    }
+
+    /// Skips the rest of the cutscene. Returns false if none is playing.
+    pub fn skip_cutscene(&mut self) -> bool {
+        self.play.cutscene.take().is_some()
+    }
+}
@@ -0,0 +1,51 @@
+//! Endings with their own titles and epilogues.
+//!
+//! Any end room finishes the game, but a room can also say which ending
+//! it is, so a maze can have a good ending, a secret one and a bad one,
+//! each with its own finish screen. A room with an ending is an end room
+//! whether or not it sets `is_end`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GameState, Phase};
+
+/// One way the game can end.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Ending {
+    pub id: String,
+    pub title: String,
+    /// Told on the finish screen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epilogue: Option<String>,
+    #[serde(default)]
+    pub kind: EndingKind,
+}
+
+/// How an ending turned out for the player, for frontends to style.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EndingKind {
+    #[default]
+    Good,
+    /// Hidden away, for players who explore
+    Secret,
+    /// The story ends badly, such as with the player's death
+    Bad,
+}
+
+impl GameState {
+    /// The ending the player reached, if finished. End rooms that do not
+    /// name an ending get a plain one with the room's id.
+    pub fn ending(&self) -> Option<Ending> {
+        let room = match &self.play.phase {
+            Phase::Finished { ending } | Phase::FreeRoam { ending } => self.get_room(ending)?,
+            Phase::Exploring | Phase::GameOver { .. } => return None,
+        };
+        Some(room.ending.clone().unwrap_or_else(|| Ending {
+            id: room.id.clone(),
+            title: "The end".to_string(),
+            epilogue: None,
+            kind: EndingKind::Good,
+        }))
+    }
+}
@@ -0,0 +1,98 @@
+//! Errors from building or loading a maze.
+
+use std::fmt;
+
+use crate::format::MazeFormat;
+
+/// Why a maze could not be built or loaded.
+#[derive(Debug)]
+pub enum Error {
+    /// The maze has no rooms, so there is nowhere to start
+    EmptyMaze,
+    /// Two rooms share this id
+    DuplicateRoomId(String),
+    /// Two items share this id
+    DuplicateItemId(String),
+    /// An exit leads to a room that does not exist
+    DanglingExit {
+        room: String,
+        label: String,
+        destination: String,
+    },
+    /// A character's reply leads to a line they do not have
+    DanglingReply {
+        npc: String,
+        label: String,
+        line: String,
+    },
+    /// A character hands over an item the maze does not have
+    UnknownGift { npc: String, item: String },
+    /// A saved game stands in a room the maze does not have
+    MissingStartRoom(String),
+    /// The file could not be read
+    Io(std::io::Error),
+    /// The file is not a valid maze
+    Parse(serde_json::Error),
+    /// The file is not a valid maze in this format
+    Syntax { format: MazeFormat, message: String },
+    /// This build was made without support for the format
+    FormatDisabled(MazeFormat),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EmptyMaze => write!(f, "the maze has no rooms"),
+            Self::DuplicateRoomId(id) => write!(f, "more than one room has the id {:?}", id),
+            Self::DuplicateItemId(id) => write!(f, "more than one item has the id {:?}", id),
+            Self::DanglingExit {
+                room,
+                label,
+                destination,
+            } => write!(
+                f,
+                "exit {:?} in room {:?} leads to unknown room {:?}",
+                label, room, destination
+            ),
+            Self::DanglingReply { npc, label, line } => write!(
+                f,
+                "reply {:?} to {:?} leads to unknown line {:?}",
+                label, npc, line
+            ),
+            Self::UnknownGift { npc, item } => {
+                write!(f, "{:?} gives unknown item {:?}", npc, item)
+            }
+            Self::MissingStartRoom(id) => write!(f, "the maze has no room {:?} to start in", id),
+            Self::Io(e) => write!(f, "could not read the maze: {}", e),
+            Self::Parse(e) => write!(f, "invalid maze file: {}", e),
+            Self::Syntax { format, message } => {
+                write!(f, "invalid {} maze file: {}", format, message)
+            }
+            Self::FormatDisabled(format) => {
+                write!(f, "this build cannot read {} maze files", format)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Parse(e)
+    }
+}
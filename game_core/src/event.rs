@@ -0,0 +1,143 @@
+//! What changed in a game, as typed events frontends can react to.
+//!
+//! Each action queues the events it caused on the game, and so does the
+//! clock running out. Frontends drain the queue with
+//! [`GameState::take_events`] after each action or frame, to play
+//! sounds, animate or log without comparing states themselves.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GameState, Phase, PlayState};
+
+/// A change in a game caused by an action.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameEvent {
+    RoomEntered {
+        from: String,
+        to: String,
+    },
+    ItemTaken(String),
+    ItemDropped(String),
+    BookmarkAdded(String),
+    BookmarkRemoved(String),
+    /// The player reached this end room
+    GameFinished {
+        ending: String,
+    },
+    FreeRoamStarted,
+    EnemyDefeated(String),
+    /// The player lost all hit points to `cause`
+    GameOver {
+        cause: String,
+    },
+    /// The player tried the exit at this index but it is closed to them
+    ExitBlocked(usize),
+}
+
+/// The parts of a play state events are derived from, captured before
+/// an action so the change can be worked out afterwards.
+pub(crate) struct Before {
+    phase: Phase,
+    inventory: Vec<String>,
+    bookmarks: Vec<String>,
+    defeated: usize,
+}
+
+impl GameState {
+    /// Events since the last call, oldest first
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+impl Before {
+    pub(crate) fn capture(play: &PlayState) -> Self {
+        Self {
+            phase: play.phase.clone(),
+            inventory: play.inventory.clone(),
+            bookmarks: play.bookmarks.clone(),
+            defeated: play.defeated.len(),
+        }
+    }
+
+    /// Events for everything that differs in `after`. Moves are queued
+    /// as they happen instead, so a room entered again still counts.
+    pub(crate) fn events(self, after: &PlayState) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        // Ids in the first list but not the second
+        let missing = |old: &[String], new: &[String]| -> Vec<String> {
+            old.iter().filter(|id| !new.contains(id)).cloned().collect()
+        };
+        let (inventory, bookmarks) = (&after.inventory, &after.bookmarks);
+        for id in missing(&self.inventory, inventory) {
+            events.push(GameEvent::ItemDropped(id));
+        }
+        for id in missing(inventory, &self.inventory) {
+            events.push(GameEvent::ItemTaken(id));
+        }
+        for id in missing(&self.bookmarks, bookmarks) {
+            events.push(GameEvent::BookmarkRemoved(id));
+        }
+        for id in missing(bookmarks, &self.bookmarks) {
+            events.push(GameEvent::BookmarkAdded(id));
+        }
+
+        for id in after.defeated.iter().skip(self.defeated) {
+            events.push(GameEvent::EnemyDefeated(id.clone()));
+        }
+
+        if after.phase != self.phase {
+            match &after.phase {
+                Phase::Finished { ending } => events.push(GameEvent::GameFinished {
+                    ending: ending.clone(),
+                }),
+                Phase::FreeRoam { .. } => events.push(GameEvent::FreeRoamStarted),
+                Phase::GameOver { cause } => events.push(GameEvent::GameOver {
+                    cause: cause.clone(),
+                }),
+                Phase::Exploring => {}
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+    use crate::action::Action;
+
+    #[test]
+    fn entering_the_same_room_again_counts() {
+        let file: MazeFile = serde_json::from_str(
+            r#"{"rooms": [
+                {"id": "a", "description": "A", "exits": [
+                    {"label": "Around", "destination": "a"},
+                    {"label": "On", "destination": "b"}
+                ]},
+                {"id": "b", "description": "B", "exits": [], "is_end": true}
+            ]}"#,
+        )
+        .unwrap();
+        let mut state = GameState::from_maze(file).unwrap();
+        let entered = |from: &str, to: &str| GameEvent::RoomEntered {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+
+        assert!(state.apply(&Action::ChooseExit(0)));
+        assert_eq!(state.take_events(), [entered("a", "a")]);
+
+        let events = state.apply_all(&[Action::ChooseExit(0), Action::ChooseExit(1)]);
+        let finished = GameEvent::GameFinished {
+            ending: "b".to_string(),
+        };
+        let expected = [entered("a", "a"), entered("a", "b"), finished];
+        assert_eq!(events, Ok(expected.to_vec()));
+        assert_eq!(state.take_events(), expected);
+    }
+}
@@ -0,0 +1,86 @@
+//! Printable gamebook version of a maze.
+//!
+//! Every room becomes a numbered section and exits read "turn to 47".
+//! The start room is always section 1; the rest are shuffled with a seed
+//! so the numbering doesn't give the solution away, but stays stable
+//! between exports.
+
+use std::collections::HashMap;
+
+use super::escape_markup;
+use crate::Room;
+use crate::rng::Rng;
+
+const STYLE: &str = "\
+body { font-family: Georgia, serif; max-width: 40em; margin: 2em auto; line-height: 1.5; }
+h1 { text-align: center; }
+.section { margin: 1.5em 0; page-break-inside: avoid; break-inside: avoid; }
+.section h2 { text-align: center; font-size: 1.4em; margin-bottom: 0.3em; }
+.exits { list-style: none; padding-left: 1em; }
+.end { text-align: center; font-weight: bold; letter-spacing: 0.2em; }
+@media print { body { margin: 0 auto; font-size: 11pt; } }
+";
+
+/// Assigns section numbers to rooms: the first room gets 1, the rest a
+/// seeded shuffle of 2..=n.
+pub fn section_numbers(rooms: &[Room], seed: u64) -> HashMap<&str, usize> {
+    let mut numbers: Vec<usize> = (2..=rooms.len()).collect();
+    Rng::new(seed).shuffle(&mut numbers);
+
+    rooms
+        .iter()
+        .map(|room| room.id.as_str())
+        .zip(std::iter::once(1).chain(numbers))
+        .collect()
+}
+
+/// Renders the rooms as a paginated, print-friendly HTML gamebook
+pub fn to_booklet_html(title: &str, rooms: &[Room], seed: u64) -> String {
+    let numbers = section_numbers(rooms, seed);
+
+    let mut by_number: Vec<(usize, &Room)> = rooms
+        .iter()
+        .map(|room| (numbers[room.id.as_str()], room))
+        .collect();
+    by_number.sort_by_key(|(number, _)| *number);
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_markup(title)));
+    html.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n", STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", escape_markup(title)));
+    html.push_str("<p>Begin at section 1.</p>\n");
+
+    for (number, room) in by_number {
+        html.push_str(&format!(
+            "<div class=\"section\" id=\"s{0}\">\n<h2>{0}</h2>\n<p>{1}</p>\n",
+            number,
+            escape_markup(&room.description)
+        ));
+
+        if room.is_end {
+            html.push_str("<p class=\"end\">THE END</p>\n");
+        }
+
+        if !room.exits.is_empty() {
+            html.push_str("<ul class=\"exits\">\n");
+            for exit in &room.exits {
+                let target = match numbers.get(exit.destination.as_str()) {
+                    Some(target) => format!("turn to <a href=\"#s{0}\">{0}</a>", target),
+                    None => "this passage leads nowhere".to_string(),
+                };
+                html.push_str(&format!(
+                    "<li>{}: {}.</li>\n",
+                    escape_markup(&exit.label),
+                    target
+                ));
+            }
+            html.push_str("</ul>\n");
+        }
+
+        html.push_str("</div>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
@@ -0,0 +1,124 @@
+//! GraphML and GEXF export for network-analysis tools such as Gephi,
+//! yEd and NetworkX.
+//!
+//! Rooms are nodes carrying their description and start/end flags; exits
+//! are directed edges labelled with the exit text. Exits to unknown rooms
+//! are skipped since these tools reject edges to missing nodes.
+
+use std::collections::HashSet;
+
+use super::escape_markup;
+use crate::{Exit, Room};
+
+/// Renders the maze as a GraphML document
+pub fn to_graphml(rooms: &[Room]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    xml.push_str(
+        "  <key id=\"description\" for=\"node\" attr.name=\"description\" attr.type=\"string\"/>\n",
+    );
+    xml.push_str(
+        "  <key id=\"is_start\" for=\"node\" attr.name=\"is_start\" attr.type=\"boolean\"/>\n",
+    );
+    xml.push_str(
+        "  <key id=\"is_end\" for=\"node\" attr.name=\"is_end\" attr.type=\"boolean\"/>\n",
+    );
+    xml.push_str("  <key id=\"label\" for=\"edge\" attr.name=\"label\" attr.type=\"string\"/>\n");
+    xml.push_str("  <graph id=\"maze\" edgedefault=\"directed\">\n");
+
+    for (i, room) in rooms.iter().enumerate() {
+        xml.push_str(&format!("    <node id=\"{}\">\n", escape_markup(&room.id)));
+        xml.push_str(&format!(
+            "      <data key=\"description\">{}</data>\n",
+            escape_markup(&room.description)
+        ));
+        xml.push_str(&format!("      <data key=\"is_start\">{}</data>\n", i == 0));
+        xml.push_str(&format!(
+            "      <data key=\"is_end\">{}</data>\n",
+            room.is_end
+        ));
+        xml.push_str("    </node>\n");
+    }
+
+    for (i, (room, exit)) in edges(rooms).into_iter().enumerate() {
+        xml.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+            i,
+            escape_markup(&room.id),
+            escape_markup(&exit.destination)
+        ));
+        xml.push_str(&format!(
+            "      <data key=\"label\">{}</data>\n",
+            escape_markup(&exit.label)
+        ));
+        xml.push_str("    </edge>\n");
+    }
+
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+/// Renders the maze as a GEXF 1.3 document
+pub fn to_gexf(title: &str, rooms: &[Room]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+    xml.push_str(&format!(
+        "  <meta>\n    <creator>Maze Game</creator>\n    <description>{}</description>\n  </meta>\n",
+        escape_markup(title)
+    ));
+    xml.push_str("  <graph defaultedgetype=\"directed\" mode=\"static\">\n");
+    xml.push_str("    <attributes class=\"node\">\n");
+    xml.push_str("      <attribute id=\"0\" title=\"description\" type=\"string\"/>\n");
+    xml.push_str("      <attribute id=\"1\" title=\"is_start\" type=\"boolean\"/>\n");
+    xml.push_str("      <attribute id=\"2\" title=\"is_end\" type=\"boolean\"/>\n");
+    xml.push_str("    </attributes>\n");
+
+    xml.push_str("    <nodes>\n");
+    for (i, room) in rooms.iter().enumerate() {
+        let id = escape_markup(&room.id);
+        xml.push_str(&format!("      <node id=\"{0}\" label=\"{0}\">\n", id));
+        xml.push_str("        <attvalues>\n");
+        xml.push_str(&format!(
+            "          <attvalue for=\"0\" value=\"{}\"/>\n",
+            escape_markup(&room.description)
+        ));
+        xml.push_str(&format!(
+            "          <attvalue for=\"1\" value=\"{}\"/>\n",
+            i == 0
+        ));
+        xml.push_str(&format!(
+            "          <attvalue for=\"2\" value=\"{}\"/>\n",
+            room.is_end
+        ));
+        xml.push_str("        </attvalues>\n");
+        xml.push_str("      </node>\n");
+    }
+    xml.push_str("    </nodes>\n");
+
+    xml.push_str("    <edges>\n");
+    for (i, (room, exit)) in edges(rooms).into_iter().enumerate() {
+        xml.push_str(&format!(
+            "      <edge id=\"{}\" source=\"{}\" target=\"{}\" label=\"{}\"/>\n",
+            i,
+            escape_markup(&room.id),
+            escape_markup(&exit.destination),
+            escape_markup(&exit.label)
+        ));
+    }
+    xml.push_str("    </edges>\n");
+
+    xml.push_str("  </graph>\n</gexf>\n");
+    xml
+}
+
+/// Every exit leading to a room that exists, with the room it leaves from
+fn edges(rooms: &[Room]) -> Vec<(&Room, &Exit)> {
+    let ids: HashSet<&str> = rooms.iter().map(|room| room.id.as_str()).collect();
+    rooms
+        .iter()
+        .flat_map(|room| room.exits.iter().map(move |exit| (room, exit)))
+        .filter(|(_, exit)| ids.contains(exit.destination.as_str()))
+        .collect()
+}
@@ -0,0 +1,235 @@
+//! Standalone interactive HTML map of a maze.
+//!
+//! The page embeds the maze as JSON alongside a small force-directed
+//! layout script, so it opens offline in any browser with no dependencies.
+
+use serde::Serialize;
+
+use super::escape_markup;
+use crate::Room;
+
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{TITLE}}</title>
+<style>
+  html, body { margin: 0; height: 100%; overflow: hidden; font-family: sans-serif; background: #1e1e24; color: #ddd; }
+  canvas { display: block; cursor: grab; }
+  #info { position: absolute; top: 12px; left: 12px; max-width: 360px; padding: 10px 14px;
+          background: rgba(0, 0, 0, 0.7); border-radius: 6px; }
+  #info h1 { font-size: 16px; margin: 0 0 6px; }
+  #info p { margin: 4px 0; font-size: 13px; }
+</style>
+</head>
+<body>
+<div id="info"><h1>{{TITLE}}</h1><p>Drag rooms to rearrange, drag the background to pan, scroll to zoom. Click a room for details.</p></div>
+<canvas id="map"></canvas>
+<script>
+const maze = {{DATA}};
+const canvas = document.getElementById("map");
+const ctx = canvas.getContext("2d");
+const info = document.getElementById("info");
+
+const nodes = maze.rooms.map((room, i) => {
+  const angle = i * 2.399963;
+  const radius = 40 * Math.sqrt(i + 1);
+  return { ...room, x: Math.cos(angle) * radius, y: Math.sin(angle) * radius, vx: 0, vy: 0 };
+});
+const index = new Map(nodes.map((node, i) => [node.id, i]));
+const edges = maze.exits
+  .filter(exit => index.has(exit.from) && index.has(exit.to))
+  .map(exit => ({ ...exit, a: index.get(exit.from), b: index.get(exit.to) }));
+
+const view = { x: 0, y: 0, scale: 1 };
+let dragging = null;
+let heat = 1;
+
+function resize() {
+  canvas.width = window.innerWidth;
+  canvas.height = window.innerHeight;
+}
+
+function step() {
+  if (heat < 0.005) return;
+  for (let i = 0; i < nodes.length; i++) {
+    for (let j = i + 1; j < nodes.length; j++) {
+      const a = nodes[i], b = nodes[j];
+      let dx = a.x - b.x, dy = a.y - b.y;
+      const d2 = Math.max(dx * dx + dy * dy, 25);
+      const f = 2500 / d2;
+      const d = Math.sqrt(d2);
+      dx /= d; dy /= d;
+      a.vx += dx * f; a.vy += dy * f;
+      b.vx -= dx * f; b.vy -= dy * f;
+    }
+  }
+  for (const e of edges) {
+    const a = nodes[e.a], b = nodes[e.b];
+    const dx = b.x - a.x, dy = b.y - a.y;
+    const d = Math.max(Math.sqrt(dx * dx + dy * dy), 1);
+    const f = (d - 110) * 0.02;
+    a.vx += dx / d * f; a.vy += dy / d * f;
+    b.vx -= dx / d * f; b.vy -= dy / d * f;
+  }
+  for (const n of nodes) {
+    if (n === dragging) continue;
+    n.vx -= n.x * 0.002; n.vy -= n.y * 0.002;
+    n.x += n.vx * heat; n.y += n.vy * heat;
+    n.vx *= 0.6; n.vy *= 0.6;
+  }
+  heat *= 0.995;
+}
+
+function toScreen(n) {
+  return [canvas.width / 2 + (n.x + view.x) * view.scale, canvas.height / 2 + (n.y + view.y) * view.scale];
+}
+
+function toWorld(sx, sy) {
+  return [(sx - canvas.width / 2) / view.scale - view.x, (sy - canvas.height / 2) / view.scale - view.y];
+}
+
+function draw() {
+  ctx.clearRect(0, 0, canvas.width, canvas.height);
+  ctx.lineWidth = 1.5;
+  ctx.font = "12px sans-serif";
+  for (const e of edges) {
+    const [ax, ay] = toScreen(nodes[e.a]);
+    const [bx, by] = toScreen(nodes[e.b]);
+    const angle = Math.atan2(by - ay, bx - ax);
+    const r = 12 * view.scale;
+    const tx = bx - Math.cos(angle) * r, ty = by - Math.sin(angle) * r;
+    ctx.strokeStyle = "#777";
+    ctx.fillStyle = "#777";
+    ctx.beginPath(); ctx.moveTo(ax, ay); ctx.lineTo(tx, ty); ctx.stroke();
+    ctx.beginPath();
+    ctx.moveTo(tx, ty);
+    ctx.lineTo(tx - Math.cos(angle - 0.4) * 8, ty - Math.sin(angle - 0.4) * 8);
+    ctx.lineTo(tx - Math.cos(angle + 0.4) * 8, ty - Math.sin(angle + 0.4) * 8);
+    ctx.fill();
+    if (view.scale > 0.7) {
+      ctx.fillStyle = "#999";
+      ctx.fillText(e.label, (ax + bx) / 2 + 4, (ay + by) / 2 - 4);
+    }
+  }
+  for (const n of nodes) {
+    const [x, y] = toScreen(n);
+    ctx.beginPath();
+    ctx.arc(x, y, 12 * view.scale, 0, Math.PI * 2);
+    ctx.fillStyle = n.start ? "#4caf50" : n.is_end ? "#e0b020" : "#4a78c8";
+    ctx.fill();
+    ctx.fillStyle = "#eee";
+    ctx.fillText(n.id, x + 14 * view.scale, y + 4);
+  }
+}
+
+function frame() {
+  step();
+  draw();
+  requestAnimationFrame(frame);
+}
+
+function nodeAt(sx, sy) {
+  const [wx, wy] = toWorld(sx, sy);
+  return nodes.find(n => (n.x - wx) ** 2 + (n.y - wy) ** 2 < 144);
+}
+
+function text(tag, value) {
+  const el = document.createElement(tag);
+  el.textContent = value;
+  return el;
+}
+
+function showRoom(n) {
+  info.replaceChildren(text("h1", n.id), text("p", n.description));
+  for (const e of edges.filter(e => nodes[e.a] === n)) {
+    info.appendChild(text("p", "→ " + e.label + " (" + e.to + ")"));
+  }
+  if (n.is_end) info.appendChild(text("p", "★ End room"));
+}
+
+let panning = null;
+canvas.addEventListener("mousedown", ev => {
+  dragging = nodeAt(ev.offsetX, ev.offsetY) || null;
+  if (dragging) { showRoom(dragging); heat = Math.max(heat, 0.3); }
+  else panning = [ev.offsetX, ev.offsetY];
+});
+canvas.addEventListener("mousemove", ev => {
+  if (dragging) {
+    [dragging.x, dragging.y] = toWorld(ev.offsetX, ev.offsetY);
+    heat = Math.max(heat, 0.3);
+  } else if (panning) {
+    view.x += (ev.offsetX - panning[0]) / view.scale;
+    view.y += (ev.offsetY - panning[1]) / view.scale;
+    panning = [ev.offsetX, ev.offsetY];
+  }
+});
+window.addEventListener("mouseup", () => { dragging = null; panning = null; });
+canvas.addEventListener("wheel", ev => {
+  ev.preventDefault();
+  view.scale = Math.min(4, Math.max(0.1, view.scale * (ev.deltaY < 0 ? 1.1 : 0.9)));
+}, { passive: false });
+window.addEventListener("resize", resize);
+resize();
+frame();
+</script>
+</body>
+</html>
+"##;
+
+#[derive(Serialize)]
+struct HtmlRoom<'a> {
+    id: &'a str,
+    description: &'a str,
+    is_end: bool,
+    start: bool,
+}
+
+#[derive(Serialize)]
+struct HtmlExit<'a> {
+    from: &'a str,
+    to: &'a str,
+    label: &'a str,
+}
+
+#[derive(Serialize)]
+struct HtmlMaze<'a> {
+    rooms: Vec<HtmlRoom<'a>>,
+    exits: Vec<HtmlExit<'a>>,
+}
+
+/// Renders the rooms as a standalone interactive HTML page.
+/// The first room is highlighted as the start.
+pub fn to_html(title: &str, rooms: &[Room]) -> String {
+    let maze = HtmlMaze {
+        rooms: rooms
+            .iter()
+            .enumerate()
+            .map(|(i, room)| HtmlRoom {
+                id: &room.id,
+                description: &room.description,
+                is_end: room.is_end,
+                start: i == 0,
+            })
+            .collect(),
+        exits: rooms
+            .iter()
+            .flat_map(|room| {
+                room.exits.iter().map(move |exit| HtmlExit {
+                    from: &room.id,
+                    to: &exit.destination,
+                    label: &exit.label,
+                })
+            })
+            .collect(),
+    };
+
+    // "</" inside the JSON would end the script element early
+    let data = serde_json::to_string(&maze)
+        .expect("maze data serializes")
+        .replace("</", "<\\/");
+
+    TEMPLATE
+        .replace("{{TITLE}}", &escape_markup(title))
+        .replace("{{DATA}}", &data)
+}
@@ -0,0 +1,181 @@
+//! Export to an Inform 7 source skeleton.
+//!
+//! Inform connects rooms by compass direction, so exits whose labels name
+//! a direction keep it; others are given a free direction with the
+//! original label left as a comment for the author to revisit.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::Room;
+
+/// Inform directions with their opposites, longest names first so
+/// "northeast" is matched before "north"
+const DIRECTIONS: &[(&str, &str)] = &[
+    ("northeast", "southwest"),
+    ("northwest", "southeast"),
+    ("southeast", "northwest"),
+    ("southwest", "northeast"),
+    ("north", "south"),
+    ("south", "north"),
+    ("east", "west"),
+    ("west", "east"),
+    ("up", "down"),
+    ("down", "up"),
+    ("inside", "outside"),
+    ("outside", "inside"),
+];
+
+/// Order in which unused directions are handed out
+const FREE_ORDER: &[&str] = &[
+    "north",
+    "east",
+    "south",
+    "west",
+    "northeast",
+    "southeast",
+    "southwest",
+    "northwest",
+    "up",
+    "down",
+    "inside",
+    "outside",
+];
+
+/// Renders the rooms as Inform 7 source with rooms, connections,
+/// descriptions and an ending for each end room
+pub fn to_inform7(title: &str, rooms: &[Room]) -> String {
+    let names = room_names(rooms);
+    let mut source = format!("\"{}\" by Anonymous\n\n", inform_text(title));
+
+    for room in rooms {
+        source.push_str(&format!(
+            "The {} is a room. \"{}\"\n\n",
+            names[room.id.as_str()],
+            inform_text(&room.description)
+        ));
+    }
+
+    // Directions already taken, keyed by (room id, direction)
+    let mut used: HashSet<(&str, &str)> = HashSet::new();
+    let mut assigned: HashMap<(&str, &str), &str> = HashMap::new();
+
+    for room in rooms {
+        for exit in &room.exits {
+            let Some(destination) = names.get(exit.destination.as_str()) else {
+                continue;
+            };
+
+            let from = room.id.as_str();
+            let to = exit.destination.as_str();
+            let named = direction_in(&exit.label).filter(|d| !used.contains(&(from, *d)));
+            let reverse = assigned
+                .get(&(to, from))
+                .map(|d| opposite(d))
+                .filter(|d| !used.contains(&(from, *d)));
+            let free = FREE_ORDER
+                .iter()
+                .copied()
+                .find(|d| !used.contains(&(from, *d)));
+            let Some(direction) = named.or(reverse).or(free) else {
+                source.push_str(&format!(
+                    "[No direction left for \"{}\" from the {} to the {}.]\n",
+                    inform_text(&exit.label),
+                    names[from],
+                    destination
+                ));
+                continue;
+            };
+
+            used.insert((from, direction));
+            assigned.insert((from, to), direction);
+
+            let mut line = format!(
+                "{} of the {} is the {}.",
+                capitalize(direction),
+                names[from],
+                destination
+            );
+            if named.is_none() {
+                line.push_str(&format!(" [{}]", inform_text(&exit.label)));
+            }
+            source.push_str(&line);
+            source.push('\n');
+        }
+    }
+
+    let endings: Vec<&Room> = rooms.iter().filter(|room| room.is_end).collect();
+    if !endings.is_empty() {
+        source.push('\n');
+    }
+    for room in endings {
+        source.push_str(&format!(
+            "Every turn when the player is in the {}: end the story finally saying \"You reached the end of the maze\".\n",
+            names[room.id.as_str()]
+        ));
+    }
+
+    source
+}
+
+/// Readable, unique Inform names for each room id
+fn room_names(rooms: &[Room]) -> HashMap<&str, String> {
+    let mut taken = HashSet::new();
+    let mut names = HashMap::new();
+    for room in rooms {
+        let words: Vec<String> = room
+            .id
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(capitalize)
+            .collect();
+        let base = if words.is_empty() {
+            "Unnamed Room".to_string()
+        } else {
+            words.join(" ")
+        };
+
+        let mut name = base.clone();
+        let mut n = 2;
+        while !taken.insert(name.clone()) {
+            name = format!("{} {}", base, n);
+            n += 1;
+        }
+        names.insert(room.id.as_str(), name);
+    }
+    names
+}
+
+/// The first Inform direction named in an exit label, if any
+fn direction_in(label: &str) -> Option<&'static str> {
+    let lower = label.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    DIRECTIONS
+        .iter()
+        .map(|(d, _)| *d)
+        .find(|d| words.contains(d))
+}
+
+fn opposite(direction: &str) -> &'static str {
+    DIRECTIONS
+        .iter()
+        .find(|(d, _)| *d == direction)
+        .map(|(_, o)| *o)
+        .unwrap_or("north")
+}
+
+/// Makes text safe inside an Inform string: double quotes become single
+/// quotes and square brackets, which start substitutions, become parentheses
+fn inform_text(text: &str) -> String {
+    text.replace('"', "'").replace('[', "(").replace(']', ")")
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,23 @@
+//! Exporters turning a maze into other formats for sharing.
+
+pub mod booklet;
+pub mod graph;
+pub mod html;
+pub mod inform;
+pub mod trizbort;
+
+/// Escapes text for use in HTML or XML content and attribute values
+pub(crate) fn escape_markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,150 @@
+//! Export to Trizbort map XML.
+//!
+//! Each room becomes a Trizbort room and each exit a one-way line. Room
+//! positions are kept when the maze has them, otherwise rooms are laid out
+//! on a simple grid.
+
+use super::escape_markup;
+use crate::{Position, Room};
+
+const ROOM_WIDTH: f32 = 96.0;
+const ROOM_HEIGHT: f32 = 64.0;
+const GRID_COLUMNS: usize = 8;
+const GRID_SPACING_X: f32 = 160.0;
+const GRID_SPACING_Y: f32 = 128.0;
+
+/// Renders the rooms as a Trizbort `.trizbort` XML document
+pub fn to_trizbort(title: &str, rooms: &[Room]) -> String {
+    let positions: Vec<Position> = rooms
+        .iter()
+        .enumerate()
+        .map(|(i, room)| room.position.unwrap_or_else(|| grid_position(i)))
+        .collect();
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<trizbort>\n  <info>\n");
+    xml.push_str(&format!("    <title>{}</title>\n", escape_markup(title)));
+    xml.push_str("  </info>\n  <map>\n");
+
+    for (i, room) in rooms.iter().enumerate() {
+        let position = positions[i];
+        let mut flags = String::new();
+        if i == 0 {
+            flags.push_str(" isStartRoom=\"yes\"");
+        }
+        if room.is_end {
+            flags.push_str(" isEndRoom=\"yes\"");
+        }
+        xml.push_str(&format!(
+            "    <room id=\"{}\" name=\"{}\" x=\"{}\" y=\"{}\" w=\"{}\" h=\"{}\"{}>\n",
+            i + 1,
+            escape_markup(&room.id),
+            position.x,
+            position.y,
+            ROOM_WIDTH,
+            ROOM_HEIGHT,
+            flags
+        ));
+        xml.push_str(&format!(
+            "      <description>{}</description>\n",
+            escape_markup(&room.description)
+        ));
+        xml.push_str("    </room>\n");
+    }
+
+    // Line ids continue after the room ids
+    let mut line_id = rooms.len();
+    for (from, room) in rooms.iter().enumerate() {
+        for exit in &room.exits {
+            let Some(to) = rooms.iter().position(|r| r.id == exit.destination) else {
+                continue;
+            };
+            line_id += 1;
+            xml.push_str(&format!(
+                "    <line id=\"{}\" flow=\"oneWay\" startText=\"{}\">\n",
+                line_id,
+                escape_markup(&exit.label)
+            ));
+            xml.push_str(&format!(
+                "      <dock index=\"0\" id=\"{}\" port=\"{}\" />\n",
+                from + 1,
+                port_toward(positions[from], positions[to])
+            ));
+            xml.push_str(&format!(
+                "      <dock index=\"1\" id=\"{}\" port=\"{}\" />\n",
+                to + 1,
+                port_toward(positions[to], positions[from])
+            ));
+            xml.push_str("    </line>\n");
+        }
+    }
+
+    xml.push_str("  </map>\n</trizbort>\n");
+    xml
+}
+
+fn grid_position(index: usize) -> Position {
+    Position {
+        x: (index % GRID_COLUMNS) as f32 * GRID_SPACING_X,
+        y: (index / GRID_COLUMNS) as f32 * GRID_SPACING_Y,
+    }
+}
+
+/// The compass port on `from` facing `to`
+fn port_toward(from: Position, to: Position) -> &'static str {
+    const PORTS: [&str; 8] = ["e", "se", "s", "sw", "w", "nw", "n", "ne"];
+
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    if dx == 0.0 && dy == 0.0 {
+        return "n";
+    }
+    // y grows downwards, so positive angles point south
+    let octant = (dy.atan2(dx) / std::f32::consts::FRAC_PI_4).round() as i32;
+    PORTS[octant.rem_euclid(8) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+    use crate::import::trizbort::parse_trizbort;
+
+    #[test]
+    fn round_trips_through_import() {
+        let file: MazeFile = serde_json::from_str(
+            r#"{"rooms": [
+                {"id": "Hall & stairs", "description": "Dust <everywhere>.", "exits": [
+                    {"label": "Up \"quickly\"", "destination": "Attic"},
+                    {"label": "Out", "destination": "Yard"}
+                ], "position": {"x": 10.0, "y": 20.0}},
+                {"id": "Attic", "description": "Low beams.", "exits": [
+                    {"label": "Down", "destination": "Hall & stairs"}
+                ]},
+                {"id": "Yard", "description": "Free at last.", "exits": [], "is_end": true}
+            ]}"#,
+        )
+        .unwrap();
+
+        let imported = parse_trizbort(&to_trizbort("Test & map", &file.rooms)).unwrap();
+
+        assert_eq!(imported.rooms.len(), file.rooms.len());
+        for (room, original) in imported.rooms.iter().zip(&file.rooms) {
+            assert_eq!(room.id, original.id);
+            assert_eq!(room.description, original.description);
+            assert_eq!(room.is_end, original.is_end);
+            let exits = |room: &Room| -> Vec<(String, String)> {
+                room.exits
+                    .iter()
+                    .map(|exit| (exit.label.clone(), exit.destination.clone()))
+                    .collect()
+            };
+            assert_eq!(exits(room), exits(original));
+        }
+        assert_eq!(
+            imported.rooms[0].position,
+            Some(Position { x: 10.0, y: 20.0 })
+        );
+        assert_eq!(imported.rooms[1].position, Some(grid_position(1)));
+    }
+}
@@ -0,0 +1,64 @@
+//! Player ratings of mazes, kept as a JSON Lines record.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage::Storage;
+
+/// A player's rating of a maze they finished.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Rating {
+    /// Name of the maze that was rated
+    pub maze: String,
+    /// One to five stars
+    pub stars: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Seconds since the Unix epoch
+    pub rated_at: u64,
+}
+
+impl Rating {
+    /// Creates a rating made now. Returns `None` unless `stars` is 1 to 5;
+    /// a blank comment is dropped.
+    pub fn new(maze: &str, stars: u8, comment: &str) -> Option<Self> {
+        if !(1..=5).contains(&stars) {
+            return None;
+        }
+        let comment = comment.trim();
+        Some(Self {
+            maze: maze.to_string(),
+            stars,
+            comment: (!comment.is_empty()).then(|| comment.to_string()),
+            rated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        })
+    }
+
+    /// Appends the rating as one line to the record under `key`
+    pub fn append_to(
+        &self,
+        storage: &dyn Storage,
+        key: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        storage.append_line(key, &serde_json::to_string(self)?)
+    }
+}
+
+/// Reads every rating saved with [`Rating::append_to`], none if there
+/// is no record yet
+pub fn load_ratings(
+    storage: &dyn Storage,
+    key: &str,
+) -> Result<Vec<Rating>, Box<dyn std::error::Error>> {
+    let text = storage.read(key)?.unwrap_or_default();
+    let mut ratings = Vec::new();
+    for line in text.lines() {
+        if !line.trim().is_empty() {
+            ratings.push(serde_json::from_str(line)?);
+        }
+    }
+    Ok(ratings)
+}
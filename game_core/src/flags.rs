@@ -0,0 +1,317 @@
+//! Named switches and the conditions mazes put on them.
+//!
+//! A game keeps a set of boolean flags, all off at the start. Scripts and
+//! frontends turn them on and off, and exits and room descriptions can
+//! depend on them, written like `found_hole && !door_locked`: names,
+//! `!`, `&&`, `||`, parentheses and `true`/`false`. `&&` binds tighter
+//! than `||`. A condition that does not parse is a load error.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Exit, GameState};
+
+/// A boolean expression over flags, kept with its source text so it is
+/// saved as written.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Condition {
+    source: String,
+    expr: Expr,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Expr {
+    Constant(bool),
+    Flag(String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// A description used in place of the room's own while its condition
+/// holds.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ConditionalDescription {
+    pub when: Condition,
+    pub description: String,
+}
+
+impl Condition {
+    /// Whether the condition holds with these flags; unset flags are off
+    pub fn holds(&self, flags: &BTreeMap<String, bool>) -> bool {
+        self.expr.eval(flags)
+    }
+}
+
+impl Expr {
+    fn eval(&self, flags: &BTreeMap<String, bool>) -> bool {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Flag(name) => flags.get(name).copied().unwrap_or(false),
+            Self::Not(inner) => !inner.eval(flags),
+            Self::And(a, b) => a.eval(flags) && b.eval(flags),
+            Self::Or(a, b) => a.eval(flags) || b.eval(flags),
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let expr = parse(source).map_err(|e| format!("{} in condition {:?}", e, source))?;
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Condition> for String {
+    fn from(condition: Condition) -> Self {
+        condition.source
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Name(String),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "{:?}", name),
+            Self::Not => f.write_str("'!'"),
+            Self::And => f.write_str("'&&'"),
+            Self::Or => f.write_str("'||'"),
+            Self::Open => f.write_str("'('"),
+            Self::Close => f.write_str("')'"),
+        }
+    }
+}
+
+fn parse(source: &str) -> Result<Expr, String> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        at: 0,
+    };
+    let expr = parser.or()?;
+    match parser.peek() {
+        Some(token) => Err(format!("unexpected {}", token)),
+        None => Ok(expr),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '!' => Token::Not,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' | '|' => {
+                if chars.next_if(|&(_, next)| next == c).is_none() {
+                    return Err(format!("expected '{0}{0}'", c));
+                }
+                if c == '&' { Token::And } else { Token::Or }
+            }
+            c if is_name_char(c) => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, next)) = chars.next_if(|&(_, next)| is_name_char(next)) {
+                    end = i + next.len_utf8();
+                }
+                Token::Name(source[start..end].to_string())
+            }
+            c => return Err(format!("unexpected {:?}", c)),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+/// Recursive descent: `||` of `&&` of optionally negated terms
+struct Parser<'a> {
+    tokens: &'a [Token],
+    at: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.at)
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        let found = self.peek() == Some(&token);
+        if found {
+            self.at += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat(Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+        while self.eat(Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.term()?));
+        }
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        if self.eat(Token::Not) {
+            return Ok(Expr::Not(Box::new(self.term()?)));
+        }
+        if self.eat(Token::Open) {
+            let expr = self.or()?;
+            if !self.eat(Token::Close) {
+                return Err("missing ')'".to_string());
+            }
+            return Ok(expr);
+        }
+        match self.tokens.get(self.at) {
+            Some(Token::Name(name)) => {
+                self.at += 1;
+                Ok(match name.as_str() {
+                    "true" => Expr::Constant(true),
+                    "false" => Expr::Constant(false),
+                    _ => Expr::Flag(name.clone()),
+                })
+            }
+            Some(token) => Err(format!("expected a flag name, found {}", token)),
+            None => Err("unexpected end".to_string()),
+        }
+    }
+}
+
+impl Exit {
+    /// Whether the player can see and take this exit with these flags
+    pub fn is_open(&self, flags: &BTreeMap<String, bool>) -> bool {
+        self.when.as_ref().is_none_or(|when| when.holds(flags))
+    }
+}
+
+impl GameState {
+    /// Whether a flag is on; flags never set are off
+    pub fn get_flag(&self, name: &str) -> bool {
+        self.play.flags.get(name).copied().unwrap_or(false)
+    }
+
+    /// Turns a flag on or off
+    pub fn set_flag(&mut self, name: &str, on: bool) {
+        self.play.flags.insert(name.to_string(), on);
+    }
+
+    /// Whether the current room has an exit at `index` that is open now,
+    /// and not closed after use
+    pub fn exit_available(&self, index: usize) -> bool {
+        self.current_room()
+            .exits
+            .get(index)
+            .is_some_and(|exit| exit.is_open(&self.play.flags))
+            && self.exit_closed(index).is_none()
+    }
+
+    /// Indices of the current room's exits that are open now
+    pub fn available_exits(&self) -> Vec<usize> {
+        let mut exits = self.shown_exits();
+        exits.retain(|&i| self.exit_closed(i).is_none());
+        exits
+    }
+
+    /// Indices of the current room's exits whose conditions hold, with
+    /// those closed after use, see [`cooldown`](crate::cooldown)
+    pub fn shown_exits(&self) -> Vec<usize> {
+        self.current_room()
+            .exits
+            .iter()
+            .enumerate()
+            .filter(|(_, exit)| exit.is_open(&self.play.flags))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holds(source: &str, on: &[&str]) -> bool {
+        let flags = on.iter().map(|name| (name.to_string(), true)).collect();
+        source.parse::<Condition>().unwrap().holds(&flags)
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and_and_and_than_or() {
+        assert!(holds("!a && b", &["b"]));
+        assert!(!holds("!a && b", &["a", "b"]));
+        assert!(holds("a || b && c", &["a"]));
+        assert!(!holds("a && b || c && d", &["a", "d"]));
+        assert!(holds("a && b || c && d", &["c", "d"]));
+    }
+
+    #[test]
+    fn parentheses_group() {
+        assert!(!holds("(a || b) && c", &["a"]));
+        assert!(holds("(a || b) && c", &["b", "c"]));
+        assert!(holds("!(a && b)", &["a"]));
+        assert!(holds("((true))", &[]));
+    }
+
+    #[test]
+    fn unknown_flags_are_off() {
+        assert!(!holds("never_set", &[]));
+        assert!(holds("!never_set", &["other"]));
+        assert!(!holds("false || never.set-flag", &[]));
+    }
+
+    #[test]
+    fn malformed_conditions_do_not_parse() {
+        for source in ["a &&", "(a", "a b", "a & b", "a || || b", ")", "", "a?"] {
+            assert!(source.parse::<Condition>().is_err(), "{:?} parsed", source);
+        }
+    }
+
+    #[test]
+    fn keeps_source_as_written() {
+        let condition: Condition = "a&&  !b".parse().unwrap();
+        assert_eq!(String::from(condition), "a&&  !b");
+    }
+}
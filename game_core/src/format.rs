@@ -0,0 +1,99 @@
+//! Maze file formats.
+//!
+//! JSON is always available. TOML, YAML and RON, which are nicer for
+//! writing long descriptions by hand, each sit behind a cargo feature of
+//! the same name.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::{Error, MazeFile};
+
+/// A format a maze file can be written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MazeFormat {
+    Json,
+    Toml,
+    Yaml,
+    Ron,
+}
+
+impl MazeFormat {
+    pub const ALL: [MazeFormat; 4] = [Self::Json, Self::Toml, Self::Yaml, Self::Ron];
+
+    /// The format a file's extension names, if it is one of ours
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+    }
+
+    /// File extensions for this format, preferred first
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Json => &["json"],
+            Self::Toml => &["toml"],
+            Self::Yaml => &["yaml", "yml"],
+            Self::Ron => &["ron"],
+        }
+    }
+
+    /// Whether this build can read the format
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Self::Json => true,
+            Self::Toml => cfg!(feature = "toml"),
+            Self::Yaml => cfg!(feature = "yaml"),
+            Self::Ron => cfg!(feature = "ron"),
+        }
+    }
+}
+
+impl fmt::Display for MazeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Json => "JSON",
+            Self::Toml => "TOML",
+            Self::Yaml => "YAML",
+            Self::Ron => "RON",
+        })
+    }
+}
+
+impl MazeFile {
+    /// Reads a maze file, in the format its extension names. Files with
+    /// any other extension are read as JSON.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let format = MazeFormat::from_path(&path).unwrap_or(MazeFormat::Json);
+        Self::load_as(path, format)
+    }
+
+    /// Reads a maze file in the given format
+    pub fn load_as<P: AsRef<Path>>(path: P, format: MazeFormat) -> Result<Self, Error> {
+        Self::parse(&std::fs::read_to_string(path)?, format)
+    }
+
+    /// Parses a maze from text in the given format
+    pub fn parse(text: &str, format: MazeFormat) -> Result<Self, Error> {
+        match format {
+            MazeFormat::Json => Ok(serde_json::from_str(text)?),
+            #[cfg(feature = "toml")]
+            MazeFormat::Toml => toml::from_str(text).map_err(|e| syntax(format, e)),
+            #[cfg(feature = "yaml")]
+            MazeFormat::Yaml => serde_yaml_ng::from_str(text).map_err(|e| syntax(format, e)),
+            #[cfg(feature = "ron")]
+            MazeFormat::Ron => ron::from_str(text).map_err(|e| syntax(format, e)),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::FormatDisabled(format)),
+        }
+    }
+}
+
+#[cfg(any(feature = "toml", feature = "yaml", feature = "ron"))]
+fn syntax(format: MazeFormat, e: impl fmt::Display) -> Error {
+    Error::Syntax {
+        format,
+        message: e.to_string(),
+    }
+}
@@ -0,0 +1,61 @@
+//! Free roam: after finishing, the player may jump to any room and see
+//! what they missed along the way.
+
+use crate::{ExitIcon, GameState, Phase};
+
+/// What a room holds that the player may have missed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoomAnnotation {
+    /// Whether the player entered the room during the run
+    pub visited: bool,
+    /// Exits marked as secret
+    pub secret_exits: usize,
+    /// Exits leading to rooms the player never entered
+    pub unexplored_exits: usize,
+}
+
+impl GameState {
+    /// Switches to free roam. Only possible once the maze is finished.
+    /// Returns whether free roam is now active.
+    pub fn start_free_roam(&mut self) -> bool {
+        if let Phase::Finished { ending } = &self.play.phase {
+            self.play.phase = Phase::FreeRoam {
+                ending: ending.clone(),
+            };
+        }
+        self.is_roaming()
+    }
+
+    pub fn is_roaming(&self) -> bool {
+        matches!(self.play.phase, Phase::FreeRoam { .. })
+    }
+
+    /// Jumps to any room while in free roam.
+    /// Returns false if not roaming, the player cannot act or the room
+    /// does not exist.
+    pub fn roam_to(&mut self, id: &str) -> bool {
+        if !self.is_roaming() || !self.can_act() || self.get_room(id).is_none() {
+            return false;
+        }
+        self.move_to(id.to_string());
+        true
+    }
+
+    /// Notes on a room for the free-roam map, or `None` if it does not exist
+    pub fn annotation(&self, id: &str) -> Option<RoomAnnotation> {
+        let room = self.get_room(id)?;
+        Some(RoomAnnotation {
+            visited: self.has_visited(id),
+            secret_exits: room
+                .exits
+                .iter()
+                .filter(|e| e.icon == Some(ExitIcon::Secret))
+                .count(),
+            unexplored_exits: room
+                .exits
+                .iter()
+                .filter(|e| !self.has_visited(&e.destination))
+                .count(),
+        })
+    }
+}
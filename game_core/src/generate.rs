@@ -0,0 +1,103 @@
+//! Random maze generation.
+//!
+//! Carves a [`GridMaze`] with a recursive backtracker, so every cell is
+//! reachable from every other by exactly one path, then gives each of its
+//! rooms a generated description. The same seed and size always give the
+//! same maze.
+
+use crate::MazeFile;
+use crate::grid::{Direction, GridMaze};
+use crate::rng::Rng;
+
+/// Smallest and largest grid side accepted by [`generate`]
+pub const SIZE_RANGE: std::ops::RangeInclusive<usize> = 2..=24;
+
+const PLACES: &[&str] = &[
+    "a narrow passage",
+    "a damp cellar",
+    "a dusty hall",
+    "a low tunnel",
+    "a moss-covered chamber",
+    "a cramped alcove",
+    "a vaulted crypt",
+    "a crumbling gallery",
+];
+
+const DETAILS: &[&str] = &[
+    "Water drips somewhere in the dark.",
+    "The air smells of old smoke.",
+    "Cobwebs hang from the ceiling.",
+    "Your footsteps echo off the walls.",
+    "Faded marks are scratched into the stone.",
+    "A cold draught tugs at your sleeve.",
+    "Loose gravel crunches underfoot.",
+    "It is very quiet here.",
+];
+
+/// Builds a random `width` × `height` maze from `seed`. Sides are
+/// clamped to [`SIZE_RANGE`]. The start is the top-left room and the end
+/// is the room furthest from it along the maze's paths.
+pub fn generate(seed: u64, width: usize, height: usize) -> MazeFile {
+    let grid = carve(seed, width, height);
+    let mut rng = Rng::new(seed);
+    let mut rooms = grid.to_rooms();
+    for room in &mut rooms {
+        room.description = describe(&mut rng, room.is_end);
+    }
+    MazeFile {
+        rooms,
+        ..Default::default()
+    }
+}
+
+/// The grid behind [`generate`], with the end cell marked
+pub fn carve(seed: u64, width: usize, height: usize) -> GridMaze {
+    let width = width.clamp(*SIZE_RANGE.start(), *SIZE_RANGE.end());
+    let height = height.clamp(*SIZE_RANGE.start(), *SIZE_RANGE.end());
+    let mut rng = Rng::new(seed);
+    let mut grid = GridMaze::new(width, height);
+
+    let mut visited = vec![false; width * height];
+    let mut distance = vec![0usize; width * height];
+    let mut stack = vec![(0, 0)];
+    visited[0] = true;
+
+    while let Some(&cell) = stack.last() {
+        let mut options: Vec<(Direction, (usize, usize))> = Direction::ALL
+            .into_iter()
+            .filter_map(|d| grid.neighbour(cell, d).map(|next| (d, next)))
+            .filter(|&(_, (x, y))| !visited[y * width + x])
+            .collect();
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        rng.shuffle(&mut options);
+        let (d, next) = options[0];
+        grid.open(cell, d);
+        visited[next.1 * width + next.0] = true;
+        distance[next.1 * width + next.0] = distance[cell.1 * width + cell.0] + 1;
+        stack.push(next);
+    }
+
+    let end = (0..distance.len())
+        .max_by_key(|&i| distance[i])
+        .unwrap_or_default();
+    grid.ends = vec![(end % width, end / width)];
+    grid
+}
+
+fn describe(rng: &mut Rng, is_end: bool) -> String {
+    let place = PLACES[rng.below(PLACES.len())];
+    if is_end {
+        return format!(
+            "You are in {}. Daylight spills in from above: the way out!",
+            place
+        );
+    }
+    format!(
+        "You are in {}. {}",
+        place,
+        DETAILS[rng.below(DETAILS.len())]
+    )
+}
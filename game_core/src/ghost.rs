@@ -0,0 +1,103 @@
+//! Ghost runs: when the player entered each room on their best finish,
+//! so later attempts at the same maze can race against it.
+//!
+//! Ghosts hold only room ids and times, and are kept in one JSON record
+//! keyed by maze name.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage::Storage;
+
+/// Entering a room, some time into a run.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RoomEntry {
+    pub room: String,
+    /// Milliseconds since the run started
+    pub at_ms: u64,
+}
+
+/// One run through a maze, in order of room entries.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Ghost {
+    pub entries: Vec<RoomEntry>,
+}
+
+impl Ghost {
+    /// Starts a run in `start_room`
+    pub fn new(start_room: &str) -> Self {
+        Self {
+            entries: vec![RoomEntry {
+                room: start_room.to_string(),
+                at_ms: 0,
+            }],
+        }
+    }
+
+    /// Notes that the player entered `room` after `elapsed`
+    pub fn record(&mut self, room: &str, elapsed: Duration) {
+        self.entries.push(RoomEntry {
+            room: room.to_string(),
+            at_ms: elapsed.as_millis() as u64,
+        });
+    }
+
+    /// How long the run took, up to its last room entry
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.entries.last().map_or(0, |entry| entry.at_ms))
+    }
+
+    /// The room the ghost was in `elapsed` into its run
+    pub fn room_at(&self, elapsed: Duration) -> Option<&str> {
+        let at_ms = elapsed.as_millis() as u64;
+        self.entries
+            .iter()
+            .take_while(|entry| entry.at_ms <= at_ms)
+            .last()
+            .map(|entry| entry.room.as_str())
+    }
+
+    /// When the ghost first entered `room`, if it ever did
+    pub fn reached(&self, room: &str) -> Option<Duration> {
+        self.entries
+            .iter()
+            .find(|entry| entry.room == room)
+            .map(|entry| Duration::from_millis(entry.at_ms))
+    }
+}
+
+/// Best runs by maze name, as saved by [`save_best`]
+pub fn load_ghosts(
+    storage: &dyn Storage,
+    key: &str,
+) -> Result<BTreeMap<String, Ghost>, Box<dyn std::error::Error>> {
+    let text = storage.read(key)?.ok_or("there are no ghost runs")?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Keeps `run` as the ghost for `maze` if it beats the saved one.
+/// Returns true if it did.
+pub fn save_best(
+    storage: &dyn Storage,
+    key: &str,
+    maze: &str,
+    run: &Ghost,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    // A missing record means there is nothing to beat, but an unreadable
+    // one is left alone rather than replaced with only this maze's run
+    let mut ghosts: BTreeMap<String, Ghost> = match storage.read(key)? {
+        Some(text) => serde_json::from_str(&text)?,
+        None => BTreeMap::new(),
+    };
+    if ghosts
+        .get(maze)
+        .is_some_and(|best| best.duration() <= run.duration())
+    {
+        return Ok(false);
+    }
+    ghosts.insert(maze.to_string(), run.clone());
+    storage.write(key, &serde_json::to_string_pretty(&ghosts)?)?;
+    Ok(true)
+}
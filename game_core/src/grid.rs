@@ -0,0 +1,249 @@
+//! Rectangular mazes described as a grid of cells and walls.
+//!
+//! A grid is far shorter to write than its room graph: it converts into
+//! one room per cell with "Go north/east/south/west" exits wherever there
+//! is no wall. Grids can also be drawn as ASCII art, see
+//! [`GridMaze::parse`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Exit, MazeFile, Position, Room};
+
+/// Grid distance between room positions, in map units
+const SPACING: f32 = 100.0;
+
+/// A side of a grid cell.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// This side's bit in [`GridMaze::walls`]
+    pub fn bit(self) -> u8 {
+        match self {
+            Self::North => 1,
+            Self::East => 2,
+            Self::South => 4,
+            Self::West => 8,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    /// `(dx, dy)` to the neighbouring cell, with y growing southwards
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::North => "north",
+            Self::East => "east",
+            Self::South => "south",
+            Self::West => "west",
+        }
+    }
+
+    /// The direction an exit label names, such as "Go north", "west" or
+    /// "S", in any case
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim().to_lowercase();
+        let word = label.strip_prefix("go ").unwrap_or(&label).trim();
+        Self::ALL
+            .into_iter()
+            .find(|d| word == d.name() || word == &d.name()[..1])
+    }
+}
+
+impl Room {
+    /// For rooms whose exits each name a different compass direction,
+    /// the exit index for each of [`Direction::ALL`]. `None` for other
+    /// rooms, and for rooms without exits.
+    pub fn compass_exits(&self) -> Option<[Option<usize>; 4]> {
+        let mut compass = [None; 4];
+        for (i, exit) in self.exits.iter().enumerate() {
+            let direction = Direction::from_label(&exit.label)?;
+            let slot = &mut compass[direction as usize];
+            if slot.replace(i).is_some() {
+                return None;
+            }
+        }
+        compass.iter().any(Option::is_some).then_some(compass)
+    }
+}
+
+/// A maze of `width` × `height` cells. The top-left cell is the start.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GridMaze {
+    pub width: usize,
+    pub height: usize,
+    /// One entry per cell, row by row, of [`Direction::bit`]s for the
+    /// walled sides. A side is walled if either cell says so; the outer
+    /// edge always is.
+    pub walls: Vec<u8>,
+    /// End cells as `(x, y)`. If empty, the bottom-right cell is the end.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ends: Vec<(usize, usize)>,
+}
+
+impl GridMaze {
+    /// A grid with every wall standing
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            walls: vec![0xf; width * height],
+            ends: Vec::new(),
+        }
+    }
+
+    /// The neighbouring cell in `direction`, if inside the grid
+    pub fn neighbour(
+        &self,
+        (x, y): (usize, usize),
+        direction: Direction,
+    ) -> Option<(usize, usize)> {
+        let (dx, dy) = direction.offset();
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Whether the player can walk from `cell` in `direction`
+    pub fn is_open(&self, cell: (usize, usize), direction: Direction) -> bool {
+        let Some(next) = self.neighbour(cell, direction) else {
+            return false;
+        };
+        let walled = |(x, y): (usize, usize), d: Direction| {
+            self.walls
+                .get(y * self.width + x)
+                .is_none_or(|walls| walls & d.bit() != 0)
+        };
+        !walled(cell, direction) && !walled(next, direction.opposite())
+    }
+
+    /// Takes down the wall between `cell` and its neighbour in `direction`
+    pub fn open(&mut self, cell: (usize, usize), direction: Direction) {
+        let Some(next) = self.neighbour(cell, direction) else {
+            return;
+        };
+        self.walls[cell.1 * self.width + cell.0] &= !direction.bit();
+        self.walls[next.1 * self.width + next.0] &= !direction.opposite().bit();
+    }
+
+    /// Reads a grid drawn in ASCII art, where cells sit at odd rows and
+    /// columns and any other character on a cell's edge is a wall. A `*`
+    /// in a cell marks an end.
+    ///
+    /// ```text
+    /// +-+-+
+    /// |   |
+    /// + +-+
+    /// |  *|
+    /// +-+-+
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let lines: Vec<Vec<char>> = text
+            .lines()
+            .map(|line| line.trim_end().chars().collect())
+            .filter(|line: &Vec<char>| !line.is_empty())
+            .collect();
+        let rows = lines.len();
+        let columns = lines.iter().map(Vec::len).max().unwrap_or(0);
+        if rows < 3 || columns < 3 {
+            return Err("a grid needs at least one cell".into());
+        }
+
+        let (width, height) = (columns / 2, rows / 2);
+        let at =
+            |row: usize, column: usize| -> char { lines[row].get(column).copied().unwrap_or(' ') };
+        let mut grid = Self::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let (row, column) = (2 * y + 1, 2 * x + 1);
+                if at(row, column) == '*' {
+                    grid.ends.push((x, y));
+                }
+                if x + 1 < width && at(row, column + 1) == ' ' {
+                    grid.open((x, y), Direction::East);
+                }
+                if y + 1 < height && row + 1 < rows && at(row + 1, column) == ' ' {
+                    grid.open((x, y), Direction::South);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Id of the room made from a cell
+    pub fn room_id((x, y): (usize, usize)) -> String {
+        format!("room-{}-{}", x, y)
+    }
+
+    /// One room per cell, row by row, with an exit through every open
+    /// side and placeholder descriptions
+    pub fn to_rooms(&self) -> Vec<Room> {
+        let default_end = (self.width.saturating_sub(1), self.height.saturating_sub(1));
+        let mut rooms = Vec::with_capacity(self.width * self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let exits = Direction::ALL
+                    .into_iter()
+                    .filter(|&d| self.is_open((x, y), d))
+                    .filter_map(|d| {
+                        Some(Exit {
+                            label: format!("Go {}", d.name()),
+                            destination: Self::room_id(self.neighbour((x, y), d)?),
+                            ..Default::default()
+                        })
+                    })
+                    .collect();
+                let is_end = if self.ends.is_empty() {
+                    (x, y) == default_end
+                } else {
+                    self.ends.contains(&(x, y))
+                };
+                rooms.push(Room {
+                    id: Self::room_id((x, y)),
+                    description: format!("You are in a room at column {}, row {}.", x + 1, y + 1),
+                    exits,
+                    is_end,
+                    position: Some(Position {
+                        x: x as f32 * SPACING,
+                        y: y as f32 * SPACING,
+                    }),
+                    ..Default::default()
+                });
+            }
+        }
+        rooms
+    }
+}
+
+impl From<&GridMaze> for MazeFile {
+    fn from(grid: &GridMaze) -> Self {
+        MazeFile {
+            rooms: grid.to_rooms(),
+            ..Default::default()
+        }
+    }
+}
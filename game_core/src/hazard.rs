@@ -0,0 +1,61 @@
+//! Traps, falls and other dangers built into a maze's layout.
+//!
+//! A room's hazard strikes each time the player walks in, and an exit's
+//! each time it is taken. Hazards cost health, stamina or both. Once the
+//! player has no stamina left, effort comes off their health instead.
+//! Endings are safe, and nothing strikes while roaming after the end.
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::combat::Fighter;
+
+/// Something in a room or on an exit that hurts the player.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Hazard {
+    /// What does the harm, for the lose screen, e.g. "the spike pit"
+    pub cause: String,
+    /// Hit points lost
+    #[serde(default)]
+    pub damage: u32,
+    /// Stamina used up, e.g. by a climb
+    #[serde(default)]
+    pub effort: u32,
+    /// Percent chance of striking, less with luck; always if unset, see
+    /// [`luck`](crate::luck)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chance_percent: Option<u8>,
+    /// Shown when the hazard strikes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Stamina a game starts with when the save does not say
+pub(crate) fn default_stamina() -> u32 {
+    Fighter::default().stamina
+}
+
+impl GameState {
+    /// Whether the maze can hurt the player at all, so frontends know
+    /// whether health and stamina are worth showing
+    pub fn uses_health(&self) -> bool {
+        self.maze.rooms.iter().any(|room| {
+            room.enemy.is_some()
+                || room.hazard.is_some()
+                || room.exits.iter().any(|exit| exit.hazard.is_some())
+        })
+    }
+
+    /// Applies a hazard, which may end the game
+    pub(crate) fn suffer(&mut self, hazard: &Hazard) {
+        if !self.hazard_strikes(hazard) {
+            return;
+        }
+        if let Some(message) = &hazard.message {
+            self.messages.push(message.clone());
+        }
+        let exhausted = hazard.effort.saturating_sub(self.play.stamina);
+        self.play.stamina = self.play.stamina.saturating_sub(hazard.effort);
+        self.take_blow(hazard.damage.saturating_add(exhausted), &hazard.cause);
+    }
+}
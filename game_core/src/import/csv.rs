@@ -0,0 +1,168 @@
+//! Import from a CSV edge list.
+//!
+//! Each row is `from,to[,label]`. Rooms are created in order of first
+//! appearance with placeholder descriptions, the first room is the start,
+//! and rooms with no outgoing edges become end rooms.
+
+use std::collections::HashMap;
+
+use crate::{Exit, MazeFile, Room};
+
+/// Builds a maze from CSV edge-list text. A leading `from,to` header
+/// row is skipped, as are blank rows.
+pub fn parse_edge_list(text: &str) -> Result<MazeFile, Box<dyn std::error::Error>> {
+    let mut rooms: Vec<Room> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    // Only the first row that isn't blank can be a header
+    let mut first = true;
+
+    for (row, fields) in parse_records(text)?.into_iter().enumerate() {
+        let fields: Vec<&str> = fields.iter().map(|f| f.trim()).collect();
+        if fields.iter().all(|f| f.is_empty()) {
+            continue;
+        }
+        if std::mem::take(&mut first) && is_header(&fields) {
+            continue;
+        }
+        let [from, to, rest @ ..] = fields.as_slice() else {
+            return Err(format!("row {}: expected from,to[,label]", row + 1).into());
+        };
+        if from.is_empty() || to.is_empty() {
+            return Err(format!("row {}: room ids must not be empty", row + 1).into());
+        }
+
+        let from = room_index(&mut rooms, &mut index, from);
+        room_index(&mut rooms, &mut index, to);
+        let label = match rest.first() {
+            Some(label) if !label.is_empty() => label.to_string(),
+            _ => format!("Go to {}", to),
+        };
+        rooms[from].exits.push(Exit {
+            label,
+            destination: to.to_string(),
+            ..Default::default()
+        });
+    }
+
+    for room in &mut rooms {
+        room.is_end = room.exits.is_empty();
+    }
+
+    Ok(MazeFile {
+        rooms,
+        ..Default::default()
+    })
+}
+
+fn is_header(fields: &[&str]) -> bool {
+    matches!(fields, [from, to, ..] if from.eq_ignore_ascii_case("from") && to.eq_ignore_ascii_case("to"))
+}
+
+/// Index of the room with this id, creating it if new
+fn room_index(rooms: &mut Vec<Room>, index: &mut HashMap<String, usize>, id: &str) -> usize {
+    *index.entry(id.to_string()).or_insert_with(|| {
+        rooms.push(Room {
+            id: id.to_string(),
+            description: format!("You are in {}.", id),
+            ..Default::default()
+        });
+        rooms.len() - 1
+    })
+}
+
+/// Splits CSV text into records, honouring double-quoted fields with
+/// `""` escapes and embedded commas or newlines
+fn parse_records(text: &str) -> Result<Vec<Vec<String>>, Box<dyn std::error::Error>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => quoted = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("unterminated quoted field".into());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_fields_keep_commas_quotes_and_newlines() {
+        let records = parse_records("a,\"say \"\"hi\"\", then\nleave\",c\r\nd,e").unwrap();
+        assert_eq!(
+            records,
+            [vec!["a", "say \"hi\", then\nleave", "c"], vec!["d", "e"]]
+        );
+    }
+
+    #[test]
+    fn unterminated_quotes_are_an_error() {
+        assert!(parse_records("a,\"b\nc,d").is_err());
+        assert!(parse_edge_list("a,\"b").is_err());
+    }
+
+    #[test]
+    fn builds_rooms_and_exits_in_order() {
+        let maze = parse_edge_list(
+            "from,to,label\nhall,yard,\"Out, quickly\"\nyard,hall\n\nhall,cellar\n",
+        )
+        .unwrap();
+        let ids: Vec<_> = maze.rooms.iter().map(|room| room.id.as_str()).collect();
+        assert_eq!(ids, ["hall", "yard", "cellar"]);
+        let labels: Vec<_> = maze.rooms[0]
+            .exits
+            .iter()
+            .map(|exit| exit.label.as_str())
+            .collect();
+        assert_eq!(labels, ["Out, quickly", "Go to cellar"]);
+        assert!(maze.rooms[2].is_end);
+        assert!(!maze.rooms[1].is_end);
+    }
+
+    #[test]
+    fn header_may_follow_blank_lines() {
+        let maze = parse_edge_list("\n \nFrom,To\na,b\n").unwrap();
+        let ids: Vec<_> = maze.rooms.iter().map(|room| room.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+    }
+
+    #[test]
+    fn short_rows_are_an_error() {
+        let error = parse_edge_list("a,b\nc\n").err().map(|e| e.to_string());
+        assert_eq!(error.as_deref(), Some("row 2: expected from,to[,label]"));
+        assert!(parse_edge_list("a,\n").is_err());
+    }
+}
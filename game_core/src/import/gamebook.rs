@@ -0,0 +1,309 @@
+//! Importer for gamebook-style numbered section text.
+//!
+//! Reads text such as `12. You are in a cave. To go left turn to 47.` and
+//! heuristically turns each numbered section into a room and each
+//! "turn to N" reference into an exit.
+
+use std::collections::HashSet;
+
+use crate::{Exit, MazeFile, Room};
+
+/// Phrases introducing a section reference, in lowercase
+const REFERENCE_PHRASES: &[&str] = &["turn to ", "go to "];
+
+/// Optional words between the phrase and the number
+const REFERENCE_NOUNS: &[&str] = &["section ", "paragraph "];
+
+/// Words joining one choice to the last, stripped before the prefixes
+const LABEL_CONJUNCTIONS: &[&str] = &["or ", "and "];
+
+/// Leading words stripped from exit labels, in lowercase
+const LABEL_PREFIXES: &[&str] = &[
+    "if you wish to ",
+    "if you want to ",
+    "if you decide to ",
+    "if you ",
+    "if ",
+    "to ",
+];
+
+/// Things a human should review after an import.
+#[derive(Clone, Debug, Default)]
+pub struct ImportReport {
+    /// References to sections that don't exist; these exits were dropped
+    pub unresolved: Vec<UnresolvedReference>,
+    /// Section numbers that appeared more than once; later copies were dropped
+    pub duplicate_sections: Vec<String>,
+    /// Sections without any references, imported as end rooms
+    pub endings: Vec<String>,
+}
+
+/// A "turn to N" pointing at a missing section.
+#[derive(Clone, Debug)]
+pub struct UnresolvedReference {
+    pub section: String,
+    pub target: String,
+}
+
+/// A reference found in the text, with its byte range
+struct Reference {
+    target: String,
+    start: usize,
+    end: usize,
+}
+
+/// Parses gamebook text into a maze. Section 1 (or the first section)
+/// becomes the start room.
+pub fn parse_gamebook(text: &str) -> (MazeFile, ImportReport) {
+    let mut report = ImportReport::default();
+    let mut seen = HashSet::new();
+    let mut rooms = Vec::new();
+
+    for (number, body) in split_sections(text) {
+        if !seen.insert(number.clone()) {
+            report.duplicate_sections.push(number);
+            continue;
+        }
+        rooms.push(parse_section(number, &body));
+    }
+
+    // Drop exits to sections that never appeared
+    for room in &mut rooms {
+        room.exits.retain(|exit| {
+            let known = seen.contains(&exit.destination);
+            if !known {
+                report.unresolved.push(UnresolvedReference {
+                    section: room.id.clone(),
+                    target: exit.destination.clone(),
+                });
+            }
+            known
+        });
+    }
+
+    for room in &mut rooms {
+        if room.exits.is_empty() {
+            room.is_end = true;
+            report.endings.push(room.id.clone());
+        }
+    }
+
+    if let Some(start) = rooms.iter().position(|room| room.id == "1") {
+        let room = rooms.remove(start);
+        rooms.insert(0, room);
+    }
+
+    let maze = MazeFile {
+        rooms,
+        ..Default::default()
+    };
+    (maze, report)
+}
+
+/// Splits the text into `(number, body)` pairs at lines starting with
+/// `N.`, `N)` or `N:`. Text before the first section is ignored.
+fn split_sections(text: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+        let marker = trimmed[digits..].chars().next();
+
+        if digits > 0 && matches!(marker, Some('.' | ')' | ':')) {
+            let number = normalize_number(&trimmed[..digits]);
+            sections.push((number, trimmed[digits + 1..].trim().to_string()));
+        } else if let Some((_, body)) = sections.last_mut() {
+            let line = line.trim();
+            if !line.is_empty() {
+                if !body.is_empty() {
+                    body.push(' ');
+                }
+                body.push_str(line);
+            }
+        }
+    }
+
+    sections
+}
+
+/// Turns one section body into a room, splitting it into descriptive
+/// clauses and exit clauses
+fn parse_section(number: String, body: &str) -> Room {
+    let mut description = String::new();
+    let mut exits = Vec::new();
+
+    for clause in split_clauses(body) {
+        let references = find_references(clause);
+        if references.is_empty() {
+            description.push_str(clause);
+            continue;
+        }
+
+        let mut label_start = 0;
+        for reference in references {
+            let label = clean_label(&clause[label_start..reference.start]);
+            exits.push(Exit {
+                label: label.unwrap_or_else(|| format!("Turn to {}", reference.target)),
+                destination: reference.target,
+                ..Default::default()
+            });
+            label_start = reference.end;
+        }
+    }
+
+    let mut description = description.trim().to_string();
+    if description.is_empty() {
+        description = body.trim().to_string();
+    }
+
+    Room {
+        id: number,
+        description,
+        exits,
+        ..Default::default()
+    }
+}
+
+/// Splits text after `.`, `!`, `?` and `;`, keeping the punctuation
+fn split_clauses(text: &str) -> Vec<&str> {
+    let mut clauses = Vec::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if matches!(c, '.' | '!' | '?' | ';') {
+            let end = i + c.len_utf8();
+            clauses.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        clauses.push(&text[start..]);
+    }
+    clauses
+}
+
+/// Finds "turn to N" style references in a clause
+fn find_references(clause: &str) -> Vec<Reference> {
+    let lower = clause.to_ascii_lowercase();
+    let mut references = Vec::new();
+    let mut from = 0;
+
+    while from < lower.len() {
+        let Some((phrase_at, phrase)) = REFERENCE_PHRASES
+            .iter()
+            .filter_map(|phrase| lower[from..].find(phrase).map(|at| (from + at, *phrase)))
+            .min_by_key(|(at, _)| *at)
+        else {
+            break;
+        };
+
+        let mut cursor = phrase_at + phrase.len();
+        if let Some(noun) = REFERENCE_NOUNS
+            .iter()
+            .find(|n| lower[cursor..].starts_with(*n))
+        {
+            cursor += noun.len();
+        }
+
+        let digits = lower[cursor..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .count();
+        if digits > 0 {
+            references.push(Reference {
+                target: normalize_number(&lower[cursor..cursor + digits]),
+                start: phrase_at,
+                end: cursor + digits,
+            });
+        }
+        from = cursor;
+    }
+
+    references
+}
+
+/// Strips leading zeros so "007" and "7" name the same section
+fn normalize_number(digits: &str) -> String {
+    let trimmed = digits.trim_start_matches('0');
+    if trimmed.is_empty() { "0" } else { trimmed }.to_string()
+}
+
+/// Tidies the text before a reference into an exit label
+fn clean_label(text: &str) -> Option<String> {
+    let mut label = text
+        .trim()
+        .trim_matches(|c: char| c == ',' || c == '.' || c.is_whitespace());
+
+    for words in [LABEL_CONJUNCTIONS, LABEL_PREFIXES] {
+        let lower = label.to_ascii_lowercase();
+        if let Some(prefix) = words.iter().find(|p| lower.starts_with(*p)) {
+            label = &label[prefix.len()..];
+        }
+    }
+    let label = label.trim_end_matches(" then").trim_end_matches(',').trim();
+
+    let lower = label.to_ascii_lowercase();
+    if label.is_empty() || lower == "or" || lower == "and" {
+        return None;
+    }
+
+    let mut chars = label.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exits(room: &Room) -> Vec<(&str, &str)> {
+        room.exits
+            .iter()
+            .map(|exit| (exit.label.as_str(), exit.destination.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn leading_zeros_name_the_same_section() {
+        let (maze, report) = parse_gamebook("001. A hall. Turn to 007.\n7. A yard.");
+        assert_eq!(maze.rooms[0].id, "1");
+        assert_eq!(exits(&maze.rooms[0]), [("Turn to 7", "7")]);
+        assert!(report.unresolved.is_empty());
+    }
+
+    #[test]
+    fn later_copies_of_a_section_are_dropped() {
+        let (maze, report) = parse_gamebook("1. Start. Turn to 2.\n2. First.\n02. Second.");
+        assert_eq!(maze.rooms.len(), 2);
+        assert_eq!(maze.rooms[1].description, "First.");
+        assert_eq!(report.duplicate_sections, ["2"]);
+    }
+
+    #[test]
+    fn references_to_missing_sections_are_reported() {
+        let (maze, report) = parse_gamebook("1. A cave. Turn to 2. Or turn to 99.\n2. Out.");
+        assert_eq!(exits(&maze.rooms[0]), [("Turn to 2", "2")]);
+        assert_eq!(report.unresolved.len(), 1);
+        assert_eq!(report.unresolved[0].section, "1");
+        assert_eq!(report.unresolved[0].target, "99");
+        assert_eq!(report.endings, ["2"]);
+    }
+
+    #[test]
+    fn section_one_starts_wherever_it_appears() {
+        let (maze, _) = parse_gamebook("Intro text.\n3. The end.\n1. Begin. Turn to 3.");
+        let ids: Vec<_> = maze.rooms.iter().map(|room| room.id.as_str()).collect();
+        assert_eq!(ids, ["1", "3"]);
+        assert!(maze.rooms[1].is_end);
+    }
+
+    #[test]
+    fn one_clause_can_hold_several_choices() {
+        let (maze, _) = parse_gamebook(
+            "1. A fork. If you go left turn to 2, or if you go right turn to section 3.\n\
+             2. Left.\n3. Right.",
+        );
+        assert_eq!(maze.rooms[0].description, "A fork.");
+        assert_eq!(exits(&maze.rooms[0]), [("Go left", "2"), ("Go right", "3")]);
+    }
+}
@@ -0,0 +1,5 @@
+//! Importers building mazes from other formats.
+
+pub mod csv;
+pub mod gamebook;
+pub mod trizbort;
@@ -0,0 +1,238 @@
+//! Import from Trizbort map XML.
+//!
+//! Trizbort rooms become rooms (named by their Trizbort name) and lines
+//! become exits: two-way lines produce an exit in each direction. Room
+//! coordinates are kept so the map can be exported again unchanged.
+
+use std::collections::{HashMap, HashSet};
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::{Exit, MazeFile, Position, Room};
+
+/// A Trizbort room as read from the XML
+#[derive(Default)]
+struct MapRoom {
+    key: String,
+    name: String,
+    position: Option<Position>,
+    description: String,
+    is_start: bool,
+    is_end: bool,
+}
+
+/// A Trizbort line as read from the XML
+#[derive(Default)]
+struct MapLine {
+    one_way: bool,
+    start_text: String,
+    end_text: String,
+    /// `(room key, port)` for dock 0 and dock 1
+    docks: [Option<(String, String)>; 2],
+}
+
+/// Parses a `.trizbort` document into a maze. The room marked as the
+/// start room (or the first room) becomes the start.
+pub fn parse_trizbort(xml: &str) -> Result<MazeFile, Box<dyn std::error::Error>> {
+    let (map_rooms, lines) = read_map(xml)?;
+
+    // Trizbort names need not be unique, room ids must be
+    let mut used = HashSet::new();
+    let mut ids: HashMap<String, String> = HashMap::new();
+    for room in &map_rooms {
+        let base = if room.name.trim().is_empty() {
+            format!("room-{}", room.key)
+        } else {
+            room.name.trim().to_string()
+        };
+        let mut id = base.clone();
+        let mut n = 2;
+        while !used.insert(id.clone()) {
+            id = format!("{} ({})", base, n);
+            n += 1;
+        }
+        ids.insert(room.key.clone(), id);
+    }
+
+    let mut rooms: Vec<Room> = map_rooms
+        .iter()
+        .map(|room| Room {
+            id: ids[&room.key].clone(),
+            description: room.description.clone(),
+            is_end: room.is_end,
+            position: room.position,
+            ..Default::default()
+        })
+        .collect();
+    let index: HashMap<&str, usize> = map_rooms
+        .iter()
+        .enumerate()
+        .map(|(i, room)| (room.key.as_str(), i))
+        .collect();
+
+    for line in &lines {
+        let [Some((a, a_port)), Some((b, b_port))] = &line.docks else {
+            continue;
+        };
+        let (Some(&a), Some(&b)) = (index.get(a.as_str()), index.get(b.as_str())) else {
+            continue;
+        };
+
+        let to_b = exit_label(&line.start_text, a_port, &rooms[b].id);
+        let destination = rooms[b].id.clone();
+        rooms[a].exits.push(Exit {
+            label: to_b,
+            destination,
+            ..Default::default()
+        });
+
+        if !line.one_way {
+            let to_a = exit_label(&line.end_text, b_port, &rooms[a].id);
+            let destination = rooms[a].id.clone();
+            rooms[b].exits.push(Exit {
+                label: to_a,
+                destination,
+                ..Default::default()
+            });
+        }
+    }
+
+    if let Some(start) = map_rooms.iter().position(|room| room.is_start) {
+        let room = rooms.remove(start);
+        rooms.insert(0, room);
+    }
+
+    Ok(MazeFile {
+        rooms,
+        ..Default::default()
+    })
+}
+
+fn read_map(xml: &str) -> Result<(Vec<MapRoom>, Vec<MapLine>), Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut rooms = Vec::new();
+    let mut lines = Vec::new();
+    let mut room: Option<MapRoom> = None;
+    let mut line: Option<MapLine> = None;
+    let mut in_description = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => match e.name().as_ref() {
+                b"room" => room = Some(read_room(&e)?),
+                b"line" => line = Some(read_line(&e)?),
+                b"dock" => read_dock(&e, line.as_mut())?,
+                b"description" => in_description = room.is_some(),
+                _ => {}
+            },
+            Event::Empty(e) => match e.name().as_ref() {
+                b"room" => rooms.push(read_room(&e)?),
+                b"line" => lines.push(read_line(&e)?),
+                b"dock" => read_dock(&e, line.as_mut())?,
+                _ => {}
+            },
+            Event::Text(text) if in_description => {
+                if let Some(room) = &mut room {
+                    room.description.push_str(&text.unescape()?);
+                }
+            }
+            Event::End(e) => match e.name().as_ref() {
+                b"room" => rooms.extend(room.take()),
+                b"line" => lines.extend(line.take()),
+                b"description" => in_description = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok((rooms, lines))
+}
+
+fn read_room(e: &BytesStart) -> Result<MapRoom, Box<dyn std::error::Error>> {
+    let mut room = MapRoom::default();
+    let (mut x, mut y) = (None, None);
+    for attr in e.attributes() {
+        let attr = attr?;
+        let value = attr.unescape_value()?;
+        match attr.key.as_ref() {
+            b"id" => room.key = value.into_owned(),
+            b"name" => room.name = value.into_owned(),
+            b"x" => x = value.parse::<f32>().ok(),
+            b"y" => y = value.parse::<f32>().ok(),
+            b"isStartRoom" => room.is_start = value == "yes",
+            b"isEndRoom" => room.is_end = value == "yes",
+            _ => {}
+        }
+    }
+    if let (Some(x), Some(y)) = (x, y) {
+        room.position = Some(Position { x, y });
+    }
+    Ok(room)
+}
+
+fn read_line(e: &BytesStart) -> Result<MapLine, Box<dyn std::error::Error>> {
+    let mut line = MapLine::default();
+    for attr in e.attributes() {
+        let attr = attr?;
+        let value = attr.unescape_value()?;
+        match attr.key.as_ref() {
+            b"flow" => line.one_way = value == "oneWay",
+            b"startText" => line.start_text = value.into_owned(),
+            b"endText" => line.end_text = value.into_owned(),
+            _ => {}
+        }
+    }
+    Ok(line)
+}
+
+fn read_dock(e: &BytesStart, line: Option<&mut MapLine>) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(line) = line else {
+        return Ok(());
+    };
+
+    let (mut index, mut key, mut port) = (None, None, String::new());
+    for attr in e.attributes() {
+        let attr = attr?;
+        let value = attr.unescape_value()?;
+        match attr.key.as_ref() {
+            b"index" => index = value.parse::<usize>().ok(),
+            b"id" => key = Some(value.into_owned()),
+            b"port" => port = value.into_owned(),
+            _ => {}
+        }
+    }
+
+    if let (Some(index @ 0..=1), Some(key)) = (index, key) {
+        line.docks[index] = Some((key, port));
+    }
+    Ok(())
+}
+
+/// Label for an exit: the line's own text, else the compass port it
+/// leaves from, else the destination's name
+fn exit_label(text: &str, port: &str, destination: &str) -> String {
+    if !text.trim().is_empty() {
+        return text.trim().to_string();
+    }
+    let direction = match port {
+        "n" => "north",
+        "ne" => "northeast",
+        "e" => "east",
+        "se" => "southeast",
+        "s" => "south",
+        "sw" => "southwest",
+        "w" => "west",
+        "nw" => "northwest",
+        "up" => "up",
+        "down" => "down",
+        "in" => "in",
+        "out" => "out",
+        _ => return format!("Go to {}", destination),
+    };
+    format!("Go {}", direction)
+}
@@ -0,0 +1,106 @@
+//! Items lying in rooms and the player's inventory.
+//!
+//! The maze says where each item starts. Picking items up and putting
+//! them down is recorded in the play state, so the maze never changes.
+
+use crate::{GameState, Item};
+
+impl GameState {
+    /// Items lying in the current room
+    pub fn items_here(&self) -> Vec<&Item> {
+        self.items_in(&self.play.current_room)
+    }
+
+    /// Items lying in a room: those that start there and are still in
+    /// place, then those dropped there
+    pub fn items_in(&self, room_id: &str) -> Vec<&Item> {
+        let Some(room) = self.get_room(room_id) else {
+            return Vec::new();
+        };
+        let mut items: Vec<&Item> = room
+            .items
+            .iter()
+            .filter(|item| {
+                !self.has_item(&item.id) && !self.play.dropped_items.contains_key(&item.id)
+            })
+            .collect();
+        for (id, room) in &self.play.dropped_items {
+            if *room == room_id
+                && let Some((_, item)) = self.maze.rooms.item(id)
+            {
+                items.push(item);
+            }
+        }
+        items
+    }
+
+    /// Items the player carries, in the order they were picked up
+    pub fn inventory(&self) -> Vec<&Item> {
+        self.play
+            .inventory
+            .iter()
+            .filter_map(|id| self.maze.rooms.item(id).map(|(_, item)| item))
+            .collect()
+    }
+
+    pub fn has_item(&self, id: &str) -> bool {
+        self.play.inventory.iter().any(|carried| carried == id)
+    }
+
+    /// Picks up an item lying in the current room.
+    /// Returns false if it is not here or the phase forbids it.
+    pub fn take_item(&mut self, id: &str) -> bool {
+        if !self.can_act() || !self.items_here().iter().any(|item| item.id == id) {
+            return false;
+        }
+        self.play.dropped_items.remove(id);
+        self.play.inventory.push(id.to_string());
+        if let Some(script) = self
+            .maze
+            .rooms
+            .item(id)
+            .and_then(|(_, item)| item.on_take.clone())
+        {
+            self.run_script(&script, &format!("on_take of {}", id));
+        }
+        true
+    }
+
+    /// Puts an item in the player's hands from wherever it is, such as
+    /// when a character hands it over. Returns false if it does not exist
+    /// or is already carried.
+    pub(crate) fn give_item(&mut self, id: &str) -> bool {
+        if self.maze.rooms.item(id).is_none() || self.has_item(id) {
+            return false;
+        }
+        self.play.dropped_items.remove(id);
+        self.play.inventory.push(id.to_string());
+        true
+    }
+
+    /// Puts a carried item down in the current room.
+    /// Returns false if the player does not carry it or the phase forbids it.
+    pub fn drop_item(&mut self, id: &str) -> bool {
+        if !self.can_act() {
+            return false;
+        }
+        let Some(i) = self.play.inventory.iter().position(|carried| carried == id) else {
+            return false;
+        };
+        self.play.inventory.remove(i);
+
+        let starts_here = self
+            .maze
+            .rooms
+            .item(id)
+            .is_some_and(|(room, _)| room.id == self.play.current_room);
+        if starts_here {
+            self.play.dropped_items.remove(id);
+        } else {
+            self.play
+                .dropped_items
+                .insert(id.to_string(), self.play.current_room.clone());
+        }
+        true
+    }
+}
@@ -0,0 +1,73 @@
+//! Pieces of a room's description that come and go with the game.
+//!
+//! A room can add sentences to its description while things stand a
+//! certain way: flags are set, the player carries an item or not, or has
+//! been to another room. [`GameState::room_description`] starts from the
+//! room's description, or the conditional one that holds, and adds every
+//! layer that holds in the room's order. Prose such as "the torch throws
+//! your shadow on the wall" then follows the game without a script.
+
+use serde::{Deserialize, Serialize};
+
+use crate::flags::Condition;
+use crate::{GameState, Room};
+
+/// Text added to a room's description while all its conditions hold. A
+/// layer without conditions is always added.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DescriptionLayer {
+    /// Added after a space, or on a line of its own if it starts with a
+    /// line break
+    pub text: String,
+    /// Flags that must be set, see [`flags`](crate::flags)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+    /// Id of an item the player must be carrying
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrying: Option<String>,
+    /// Id of an item the player must not be carrying
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_carrying: Option<String>,
+    /// Id of a room the player must have been to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visited: Option<String>,
+}
+
+impl GameState {
+    /// A room's description as things stand: the first conditional one
+    /// that holds, else its own, followed by the layers that hold
+    pub fn room_description(&self, room: &Room) -> String {
+        let base = room
+            .conditional_descriptions
+            .iter()
+            .find(|variant| variant.when.holds(&self.play.flags))
+            .map_or(&room.description, |variant| &variant.description);
+        let mut description = base.clone();
+        for layer in room
+            .description_layers
+            .iter()
+            .filter(|layer| self.layer_holds(layer))
+        {
+            if !description.is_empty() && !layer.text.starts_with('\n') {
+                description.push(' ');
+            }
+            description.push_str(&layer.text);
+        }
+        description
+    }
+
+    fn layer_holds(&self, layer: &DescriptionLayer) -> bool {
+        let flags = &self.play.flags;
+        let carried = |item: &Option<String>, wanted| {
+            item.as_deref()
+                .is_none_or(|item| self.has_item(item) == wanted)
+        };
+        layer.when.as_ref().is_none_or(|when| when.holds(flags))
+            && carried(&layer.carrying, true)
+            && carried(&layer.not_carrying, false)
+            && layer
+                .visited
+                .as_deref()
+                .is_none_or(|room| self.has_visited(room))
+    }
+}
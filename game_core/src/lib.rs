@@ -1,61 +1,741 @@
-use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
-#[derive(Serialize, Deserialize, Clone)]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub mod achievements;
+pub mod action;
+pub mod ambience;
+pub mod analysis;
+pub mod collection;
+pub mod combat;
+pub mod content_filter;
+pub mod cooldown;
+pub mod cutscene;
+pub mod ending;
+mod error;
+pub mod event;
+pub mod export;
+pub mod feedback;
+pub mod flags;
+pub mod format;
+pub mod free_roam;
+pub mod generate;
+pub mod ghost;
+pub mod grid;
+pub mod hazard;
+pub mod import;
+pub mod inventory;
+pub mod layers;
+pub mod locale;
+pub mod luck;
+pub mod matching;
+pub mod minimap;
+pub mod music;
+pub mod npc;
+pub mod replay;
+pub mod rng;
+pub mod save;
+pub mod score;
+pub mod script;
+pub mod simulate;
+pub mod solve;
+pub mod sound;
+pub mod storage;
+pub mod timeline;
+pub mod timer;
+pub mod travel;
+pub mod undo;
+pub mod validate;
+pub mod verify;
+pub mod view;
+
+pub use error::Error;
+
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Room {
     pub id: String,
     pub description: String,
     pub exits: Vec<Exit>,
 
     #[serde(default)]
-    pub is_end: bool
+    pub is_end: bool,
+
+    /// Which ending this end room is, see [`ending`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ending: Option<ending::Ending>,
+
+    /// Words describing the place, such as "water", for [`ambience`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Where the room sits on an author's map, if laid out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+
+    /// How frontends arrange the exits; chosen by exit count if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<ExitLayout>,
+
+    /// Author's notes on the room, shown in commentary mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commentary: Option<String>,
+
+    /// Items lying in the room at the start
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<Item>,
+
+    /// Plays on entering, see [`cutscene`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cutscene: Option<cutscene::Cutscene>,
+
+    /// Strikes each time the player walks in, see [`hazard`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hazard: Option<hazard::Hazard>,
+
+    /// Fights the player on entering, until beaten, see [`combat`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enemy: Option<combat::Enemy>,
+
+    /// Characters standing in the room, see [`npc`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub npcs: Vec<npc::Npc>,
+
+    /// Descriptions used instead of `description` while their
+    /// conditions hold, the first that holds winning
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditional_descriptions: Vec<flags::ConditionalDescription>,
+
+    /// Text added to the description while its conditions hold, in
+    /// order, see [`layers`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub description_layers: Vec<layers::DescriptionLayer>,
+
+    /// Script run when the player comes in, see [`script`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_enter: Option<String>,
+
+    /// Script run when the player leaves by an exit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_exit: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+/// Something the player can pick up and carry.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct Item {
+    /// Unique across the maze
+    pub id: String,
+    /// Shown to the player, e.g. "a rusty key"
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Script run when the player picks the item up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_take: Option<String>,
+}
+
+/// Arrangement of a room's exit choices.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitLayout {
+    /// One choice per line
+    Stack,
+    /// Two columns of choices
+    TwoColumns,
+    /// Choices flow left to right and wrap
+    Wrap,
+    /// A scrollable list with a filter box
+    Scroll,
+}
+
+impl ExitLayout {
+    /// Layout suited to a room with this many exits
+    pub fn for_exit_count(count: usize) -> Self {
+        match count {
+            0..=7 => Self::Stack,
+            8..=14 => Self::TwoColumns,
+            15..=29 => Self::Wrap,
+            _ => Self::Scroll,
+        }
+    }
+}
+
+impl Room {
+    /// The author's layout for this room, or one picked by exit count
+    pub fn exit_layout(&self) -> ExitLayout {
+        self.layout
+            .unwrap_or_else(|| ExitLayout::for_exit_count(self.exits.len()))
+    }
+
+    /// Exits split by group: ungrouped exits first, then each group in
+    /// order of first appearance
+    pub fn exit_groups(&self) -> Vec<ExitGroup<'_>> {
+        let mut groups = vec![ExitGroup {
+            name: None,
+            exits: Vec::new(),
+        }];
+        for (i, exit) in self.exits.iter().enumerate() {
+            let name = exit.group.as_deref();
+            match groups.iter_mut().find(|g| g.name == name) {
+                Some(group) => group.exits.push((i, exit)),
+                None => groups.push(ExitGroup {
+                    name,
+                    exits: vec![(i, exit)],
+                }),
+            }
+        }
+        groups.retain(|g| !g.exits.is_empty());
+        groups
+    }
+}
+
+/// Map coordinates of a room, y increasing downwards
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Exit {
-    pub label: String,   // e.g. "Go through the left door"
+    pub label: String,       // e.g. "Go through the left door"
     pub destination: String, // e.g. "middle"
+
+    /// Icon shown next to the label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<ExitIcon>,
+
+    /// Colour frontends may use for the exit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorHint>,
+
+    /// Section the exit is listed under, e.g. "Stairs"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+
+    /// Flags that must be set for the exit to be shown and taken, see
+    /// [`flags`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<flags::Condition>,
+
+    /// Strikes each time the exit is taken, such as a fall, see [`hazard`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hazard: Option<hazard::Hazard>,
+
+    /// Places the exit may lead instead of `destination`, drawn each time
+    /// it is taken, see [`luck`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outcomes: Vec<luck::Outcome>,
+
+    /// Whether the exit can be taken only once, see [`cooldown`]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub once: bool,
+
+    /// Turns the exit stays closed after each use, see [`cooldown`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown_turns: Option<u32>,
+}
+
+/// Exits sharing a group, with their indices in the room.
+pub struct ExitGroup<'a> {
+    /// `None` for exits without a group
+    pub name: Option<&'a str>,
+    pub exits: Vec<(usize, &'a Exit)>,
+}
+
+/// Built-in icons an exit can declare.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitIcon {
+    Door,
+    Stairs,
+    Up,
+    Down,
+    Left,
+    Right,
+    Key,
+    Lock,
+    Danger,
+    Secret,
+    Water,
+    Fire,
+    Forest,
+    Treasure,
+}
+
+/// An RGB colour written as `"#rrggbb"` in maze files.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct ColorHint {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl TryFrom<String> for ColorHint {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let hex = value
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| format!("invalid colour {:?}, expected \"#rrggbb\"", value))?;
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).expect("validated hex");
+        Ok(Self {
+            r: channel(0),
+            g: channel(2),
+            b: channel(4),
+        })
+    }
+}
+
+impl From<ColorHint> for String {
+    fn from(color: ColorHint) -> Self {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    }
 }
 
+/// A game: the maze being played and the player's progress through it.
+/// Serialized as one flat object, as saves have always been.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct GameState {
-    pub rooms: Vec<Room>,
-    pub current_room: String,
-    pub is_finished: bool,
+    #[serde(flatten)]
+    pub maze: Maze,
+    #[serde(flatten)]
+    pub play: PlayState,
+
+    /// Earlier and undone play states
+    #[serde(skip)]
+    undo: undo::UndoHistory,
+
+    /// Text printed by scripts since the frontend last asked
+    #[serde(skip)]
+    messages: Vec<String>,
+
+    /// Events since the frontend last asked
+    #[serde(skip)]
+    events: Vec<event::GameEvent>,
 }
 
+/// A playable maze: its rooms and settings. Nothing in it changes during
+/// a game, and clones share the rooms.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Maze {
+    pub rooms: RoomTable,
+
+    /// Whether the maze lets players jump to visited rooms before finishing
+    #[serde(default)]
+    pub allow_fast_travel: bool,
 
+    /// The player's hit points and attack at the start
+    #[serde(default)]
+    pub player: combat::Fighter,
+
+    /// Looping sounds for room tags, by tag
+    #[serde(default)]
+    pub ambience: BTreeMap<String, ambience::AmbientLayer>,
+
+    /// Seconds the player has to finish, if limited
+    #[serde(default)]
+    pub time_limit_secs: Option<u64>,
+
+    /// Layered music that follows how tense the game is
+    #[serde(default)]
+    pub music: Option<music::Music>,
+
+    /// Sounds for moving, picking up items, winning and the like
+    #[serde(default, skip_serializing_if = "sound::SoundEffects::is_empty")]
+    pub sounds: sound::SoundEffects,
+
+    /// Language the rooms are written in, as a tag such as `"he"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// One player's progress through a maze.
 #[derive(Serialize, Deserialize, Clone)]
+pub struct PlayState {
+    pub current_room: String,
+
+    /// What the player is doing, which decides what they may do next
+    #[serde(default)]
+    pub phase: Phase,
+
+    /// Ids of rooms the player has entered, in order of first visit
+    #[serde(default)]
+    pub visited: Vec<String>,
+
+    /// Rooms the player has bookmarked
+    #[serde(default)]
+    pub bookmarks: Vec<String>,
+
+    /// Ids of carried items, in the order they were picked up
+    #[serde(default)]
+    pub inventory: Vec<String>,
+
+    /// Items put down away from where they started: item id to room id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dropped_items: BTreeMap<String, String>,
+
+    /// Named switches set by scripts and the frontend, see [`flags`]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub flags: BTreeMap<String, bool>,
+
+    /// The character being talked to, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation: Option<npc::Conversation>,
+
+    /// Hit points left; the game is lost at zero
+    #[serde(default = "combat::default_health")]
+    pub health: u32,
+
+    /// Effort left before climbs and the like start to hurt
+    #[serde(default = "hazard::default_stamina")]
+    pub stamina: u32,
+
+    /// The fight going on, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub combat: Option<combat::Combat>,
+
+    /// Ids of the enemies beaten, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defeated: Vec<String>,
+
+    /// The cutscene playing, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cutscene: Option<cutscene::Playback>,
+
+    /// Exits taken before finishing, for the [`score`]
+    #[serde(default)]
+    pub steps: u32,
+
+    /// Exits taken, roaming included, which [`cooldown`]s count in
+    #[serde(default)]
+    pub turns: u32,
+
+    /// When each exit that closes after use was last taken, by room, see
+    /// [`cooldown`]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub exit_uses: BTreeMap<String, Vec<cooldown::ExitUse>>,
+
+    /// Time played, in milliseconds, for the [`timer`]
+    #[serde(default)]
+    pub elapsed_ms: u64,
+
+    /// Hints asked for, counted against the [`score`]
+    #[serde(default)]
+    pub hints_used: u32,
+
+    /// State of the generator random exits and hazards draw from
+    #[serde(default)]
+    pub rng: u64,
+
+    /// Tokens left for redrawing a random exit, see [`luck`]
+    #[serde(default)]
+    pub rerolls: u32,
+
+    /// What a reroll would go back to, straight after a random exit
+    #[serde(skip)]
+    pub(crate) redraw: Option<luck::Redraw>,
+}
+
+/// The stage a game is in. Each phase allows only some actions, and
+/// the game ignores the rest.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Phase {
+    /// Walking the maze towards an end room
+    #[default]
+    Exploring,
+    /// Reached the end room `ending`
+    Finished { ending: String },
+    /// Exploring freely after finishing; moves no longer count as visits
+    FreeRoam { ending: String },
+    /// Lost all hit points to `cause`, such as an enemy's name
+    GameOver { cause: String },
+}
+
+impl Phase {
+    /// Whether the player has reached an end room, now or before
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Finished { .. } | Self::FreeRoam { .. })
+    }
+
+    /// Whether exits may be taken
+    pub fn can_move(&self) -> bool {
+        matches!(self, Self::Exploring | Self::FreeRoam { .. })
+    }
+}
+
+/// A maze's rooms with a lookup by id. Read-only once built, so the
+/// lookup always matches the list. Clones share the same rooms, so many
+/// games of one maze cost one copy of it. Serialized as a plain list.
+#[derive(Clone, Default)]
+pub struct RoomTable {
+    data: Arc<RoomData>,
+}
+
+#[derive(Default)]
+struct RoomData {
+    rooms: Vec<Room>,
+    index: HashMap<String, usize>,
+    /// Item id to the room and position it starts at
+    items: HashMap<String, (usize, usize)>,
+}
+
+impl RoomTable {
+    /// The room with this id; the first one if ids repeat
+    pub fn get(&self, id: &str) -> Option<&Room> {
+        self.data.index.get(id).map(|&i| &self.data.rooms[i])
+    }
+
+    /// The item with this id and the room it starts in
+    pub fn item(&self, id: &str) -> Option<(&Room, &Item)> {
+        let &(room, item) = self.data.items.get(id)?;
+        let room = &self.data.rooms[room];
+        Some((room, &room.items[item]))
+    }
+}
+
+impl From<Vec<Room>> for RoomTable {
+    fn from(rooms: Vec<Room>) -> Self {
+        let mut index = HashMap::with_capacity(rooms.len());
+        let mut items = HashMap::new();
+        for (i, room) in rooms.iter().enumerate() {
+            index.entry(room.id.clone()).or_insert(i);
+            for (j, item) in room.items.iter().enumerate() {
+                items.entry(item.id.clone()).or_insert((i, j));
+            }
+        }
+        Self {
+            data: Arc::new(RoomData {
+                rooms,
+                index,
+                items,
+            }),
+        }
+    }
+}
+
+impl std::ops::Deref for RoomTable {
+    type Target = [Room];
+
+    fn deref(&self) -> &[Room] {
+        &self.data.rooms
+    }
+}
+
+impl<'a> IntoIterator for &'a RoomTable {
+    type Item = &'a Room;
+    type IntoIter = std::slice::Iter<'a, Room>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.rooms.iter()
+    }
+}
+
+impl Serialize for RoomTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.data.rooms.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RoomTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Room>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct MazeFile {
     pub rooms: Vec<Room>,
+
+    /// Presentation hints for frontends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<TextStyle>,
+
+    /// Let players fast-travel to visited rooms before finishing
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_fast_travel: bool,
+
+    /// The player's strength in fights; a default one if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player: Option<combat::Fighter>,
+
+    /// Looping sounds for room tags, by tag, see [`ambience`]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ambience: BTreeMap<String, ambience::AmbientLayer>,
+
+    /// Seconds the player has to finish, see [`timer`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit_secs: Option<u64>,
+
+    /// Calm, tense and chase tracks, see [`music`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music: Option<music::Music>,
+
+    /// Sounds played as things happen, see [`sound`]
+    #[serde(default, skip_serializing_if = "sound::SoundEffects::is_empty")]
+    pub sounds: sound::SoundEffects,
+
+    /// Language the rooms are written in, such as `"ar"`, which decides
+    /// which way their text runs, see [`locale`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Author-chosen text styling for the room description.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct TextStyle {
+    /// TTF/OTF file, relative to the maze file
+    pub font: Option<String>,
+    /// Base text size in points
+    pub size: Option<f32>,
+}
+
+impl Maze {
+    /// Checks a maze file and builds the maze from it. Fails if there are
+    /// no rooms, ids repeat or an exit leads nowhere.
+    pub fn new(file: MazeFile) -> Result<Self, Error> {
+        check_rooms(&file.rooms)?;
+        let mut rooms = file.rooms;
+        for room in &mut rooms {
+            room.is_end |= room.ending.is_some();
+        }
+        Ok(Self {
+            rooms: rooms.into(),
+            allow_fast_travel: file.allow_fast_travel,
+            player: file.player.unwrap_or_default(),
+            ambience: file.ambience,
+            time_limit_secs: file.time_limit_secs,
+            music: file.music,
+            sounds: file.sounds,
+            language: file.language,
+        })
+    }
+
+    /// The room every game starts in
+    pub fn start_room(&self) -> &Room {
+        &self.rooms[0]
+    }
+
+    /// Looks up a room by id
+    pub fn get_room(&self, id: &str) -> Option<&Room> {
+        self.rooms.get(id)
+    }
+
+    /// Which way the rooms' text runs, left to right unless the maze
+    /// says it is written in a right-to-left language
+    pub fn text_direction(&self) -> locale::TextDirection {
+        self.language
+            .as_deref()
+            .and_then(locale::Locale::parse)
+            .map(|locale| locale.direction())
+            .unwrap_or_default()
+    }
+}
+
+impl PlayState {
+    pub fn is_finished(&self) -> bool {
+        self.phase.is_finished()
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        matches!(self.phase, Phase::GameOver { .. })
+    }
+
+    /// A fresh game standing in the maze's start room
+    pub fn new(maze: &Maze) -> Self {
+        let start_room = maze.start_room().id.clone();
+        // The start room's cutscene serves as an introduction
+        let intro = maze
+            .start_room()
+            .cutscene
+            .as_ref()
+            .filter(|cutscene| !cutscene.beats.is_empty())
+            .map(|_| cutscene::Playback {
+                room: start_room.clone(),
+                beat: 0,
+            });
+        Self {
+            visited: vec![start_room.clone()],
+            current_room: start_room,
+            phase: Phase::Exploring,
+            bookmarks: Vec::new(),
+            inventory: Vec::new(),
+            dropped_items: BTreeMap::new(),
+            flags: BTreeMap::new(),
+            conversation: None,
+            health: maze.player.hp,
+            stamina: maze.player.stamina,
+            combat: None,
+            defeated: Vec::new(),
+            cutscene: intro,
+            steps: 0,
+            turns: 0,
+            exit_uses: BTreeMap::new(),
+            elapsed_ms: 0,
+            hints_used: 0,
+            rng: 0,
+            rerolls: maze.player.rerolls,
+            redraw: None,
+        }
+    }
 }
 
 impl GameState {
     /// Creates a new game state with the default built-in maze
     pub fn new() -> Self {
-        Self::from_rooms(Self::default_rooms())
+        Self::from_rooms(Self::default_rooms()).expect("built-in maze is valid")
     }
 
-    /// Creates a new game state from the given rooms
-    pub fn from_rooms(rooms: Vec<Room>) -> Self {
-        if rooms.is_empty() {
-            panic!("Maze must have at least one room");
-        }
-        
-        // clone to prevent BC issue, conflicts with Self::rooms below
-        let start_room: String = rooms[0].id.clone();
-        
+    /// Creates a new game state from the given rooms, starting in the
+    /// first. Fails if there are no rooms, ids repeat or an exit leads
+    /// nowhere.
+    pub fn from_rooms(rooms: Vec<Room>) -> Result<Self, Error> {
+        Self::from_maze(MazeFile {
+            rooms,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a new game state from a maze file, with its settings
+    pub fn from_maze(maze: MazeFile) -> Result<Self, Error> {
+        Ok(Self::start(Maze::new(maze)?))
+    }
+
+    /// A fresh game of `maze`
+    pub fn start(maze: Maze) -> Self {
         Self {
-            rooms, // "rooms" moved here
-            current_room: start_room, 
-            is_finished: false,
+            play: PlayState::new(&maze),
+            maze,
+            undo: Default::default(),
+            messages: Vec::new(),
+            events: Vec::new(),
         }
     }
 
+    /// Starts the same maze over from the beginning
+    pub fn restart(&mut self) {
+        self.play = PlayState::new(&self.maze);
+        self.undo.clear();
+        self.messages.clear();
+        self.events.clear();
+    }
+
     /// Loads a maze from a JSON file
-    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
-        let file = std::fs::File::open(path)?;
-        let maze_file: MazeFile = serde_json::from_reader(file)?;
-        Ok(Self::from_rooms(maze_file.rooms))
+    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        Self::from_maze(MazeFile::load(path)?)
+    }
+
+    /// Checks a state that was not built here, such as a saved game,
+    /// against the same rules as [`GameState::from_rooms`]
+    pub fn check(&self) -> Result<(), Error> {
+        check_rooms(&self.maze.rooms)?;
+        if self.get_room(&self.play.current_room).is_none() {
+            return Err(Error::MissingStartRoom(self.play.current_room.clone()));
+        }
+        Ok(())
     }
 
     /// Returns the default built-in maze rooms
@@ -67,56 +747,233 @@ impl GameState {
                 exits: vec![Exit {
                     label: "Go through the door".to_string(),
                     destination: "middle".to_string(),
+                    ..Default::default()
                 }],
-                is_end: false
+                ..Default::default()
             },
             Room {
                 id: "middle".to_string(),
-                description: "You stand in a long hallway. There is a door behind and one ahead.".to_string(),
+                description: "You stand in a long hallway. There is a door behind and one ahead."
+                    .to_string(),
                 exits: vec![
                     Exit {
                         label: "Go back".to_string(),
                         destination: "start".to_string(),
+                        ..Default::default()
                     },
                     Exit {
                         label: "Go forward".to_string(),
                         destination: "end".to_string(),
+                        ..Default::default()
                     },
                 ],
-                is_end: false
+                ..Default::default()
             },
             Room {
                 id: "end".to_string(),
-                description: "You find yourself in a bright room — the end of the maze!".to_string(),
+                description: "You find yourself in a bright room — the end of the maze!"
+                    .to_string(),
                 exits: vec![],
-                is_end: true 
+                is_end: true,
+                ..Default::default()
             },
         ]
     }
 
     pub fn current_room(&self) -> &Room {
-        self.rooms
-            .iter()
-            .find(|r| r.id == self.current_room)
+        self.get_room(&self.play.current_room)
             .expect("current room exists")
     }
 
+    /// Looks up a room by id
+    pub fn get_room(&self, id: &str) -> Option<&Room> {
+        self.maze.get_room(id)
+    }
+
+    /// Takes the exit at `index`, if the player [can act](Self::can_act)
     pub fn choose_exit(&mut self, index: usize) {
-        
+        if !self.can_act() {
+            return;
+        }
+
         // First, get the destination using only immutable access
-        let destination = self.current_room()
+        let exit = self
+            .current_room()
             .exits
             .get(index)
-            .map(|exit| exit.destination.clone());
+            .filter(|exit| exit.is_open(&self.play.flags) && self.exit_closed(index).is_none())
+            .map(|exit| {
+                (
+                    exit.destination.clone(),
+                    exit.hazard.clone(),
+                    !exit.outcomes.is_empty(),
+                )
+            });
 
-        let is_end = self.current_room().is_end.clone();
-        
         // Now we can use the destination with mutable access
-        if let Some(dest) = destination {
-            self.current_room = dest;
-            if is_end {
-                self.is_finished = true;
+        if let Some((dest, hazard, random)) = exit {
+            let before = random.then(|| self.play.clone());
+            self.play.redraw = None;
+            let dest = if random {
+                self.draw_destination(index).unwrap_or(dest)
+            } else {
+                dest
+            };
+            if let Some(script) = self.current_room().on_exit.clone() {
+                self.run_script(&script, &format!("on_exit of {}", self.play.current_room));
+            }
+            if let Some(hazard) = hazard.filter(|_| !self.is_roaming()) {
+                self.suffer(&hazard);
+                // A fatal fall leaves the player where they fell from
+                if self.play.is_game_over() {
+                    return;
+                }
+            }
+            let left = self.play.current_room.clone();
+            self.enter_room(dest);
+            self.record_exit_use(left, index);
+            if let Some(before) = before {
+                self.allow_reroll(before, index);
+            }
+        }
+    }
+
+    /// Puts the player in a room, leaving any conversation or cutscene,
+    /// and queues [`event::GameEvent::RoomEntered`]. Returns the room left.
+    pub(crate) fn move_to(&mut self, id: String) -> String {
+        let from = std::mem::replace(&mut self.play.current_room, id);
+        self.play.conversation = None;
+        self.play.cutscene = None;
+        self.events.push(event::GameEvent::RoomEntered {
+            from: from.clone(),
+            to: self.play.current_room.clone(),
+        });
+        from
+    }
+
+    /// Moves the player into a room, recording the visit
+    fn enter_room(&mut self, id: String) {
+        let play = &mut self.play;
+        let roaming = matches!(play.phase, Phase::FreeRoam { .. });
+        let first_visit = !play.visited.contains(&id);
+        if !roaming && first_visit {
+            play.visited.push(id.clone());
+        }
+        if !roaming {
+            play.steps += 1;
+        }
+        play.turns += 1;
+        let from = self.move_to(id);
+        if !roaming {
+            self.start_cutscene(first_visit);
+        }
+        if !roaming && self.current_room().is_end {
+            self.play.phase = Phase::Finished {
+                ending: self.play.current_room.clone(),
+            };
+        }
+        if let Some(script) = self.current_room().on_enter.clone() {
+            self.run_script(&script, &format!("on_enter of {}", self.play.current_room));
+        }
+        if !roaming
+            && self.play.phase.can_move()
+            && let Some(hazard) = self.current_room().hazard.clone()
+        {
+            self.suffer(&hazard);
+        }
+        if !roaming && self.play.phase.can_move() {
+            self.meet_enemy(from);
+        }
+    }
+
+    pub fn has_visited(&self, id: &str) -> bool {
+        self.play.visited.iter().any(|v| v == id)
+    }
+
+    /// What stops the player taking exits, talking or handling items
+    /// now, if anything: a phase that does not allow it, a fight or a
+    /// cutscene. A conversation does not, since walking off ends it.
+    pub fn held_back(&self) -> Option<&'static str> {
+        if !self.play.phase.can_move() {
+            Some("The game is over")
+        } else if self.in_combat() {
+            Some("You are in a fight")
+        } else if self.in_cutscene() {
+            Some("A cutscene is playing")
+        } else {
+            None
+        }
+    }
+
+    /// Whether the player may take exits, talk and handle items now
+    pub fn can_act(&self) -> bool {
+        self.held_back().is_none()
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rejects mazes a game cannot be played on
+fn check_rooms(rooms: &[Room]) -> Result<(), Error> {
+    if rooms.is_empty() {
+        return Err(Error::EmptyMaze);
+    }
+
+    let mut ids = std::collections::HashSet::new();
+    for room in rooms {
+        if !ids.insert(room.id.as_str()) {
+            return Err(Error::DuplicateRoomId(room.id.clone()));
+        }
+    }
+
+    for room in rooms {
+        for exit in &room.exits {
+            if let Some(destination) = exit
+                .destinations()
+                .find(|destination| !ids.contains(destination))
+            {
+                return Err(Error::DanglingExit {
+                    room: room.id.clone(),
+                    label: exit.label.clone(),
+                    destination: destination.to_string(),
+                });
+            }
+        }
+    }
+
+    let mut items = std::collections::HashSet::new();
+    for item in rooms.iter().flat_map(|room| &room.items) {
+        if !items.insert(item.id.as_str()) {
+            return Err(Error::DuplicateItemId(item.id.clone()));
+        }
+    }
+
+    for npc in rooms.iter().flat_map(|room| &room.npcs) {
+        for reply in npc.dialogue.iter().flat_map(|line| &line.replies) {
+            if let Some(next) = &reply.next
+                && npc.line(next).is_none()
+            {
+                return Err(Error::DanglingReply {
+                    npc: npc.id.clone(),
+                    label: reply.label.clone(),
+                    line: next.clone(),
+                });
+            }
+            if let Some(item) = reply
+                .give_items
+                .iter()
+                .find(|id| !items.contains(id.as_str()))
+            {
+                return Err(Error::UnknownGift {
+                    npc: npc.id.clone(),
+                    item: item.clone(),
+                });
             }
         }
     }
+    Ok(())
 }
@@ -0,0 +1,239 @@
+//! Numbers, times and dates written the way the player's language
+//! writes them, and which way its text runs.
+//!
+//! A [`Locale`] is read from a tag such as `"de-DE"` or `"ar_EG.UTF-8"`.
+//! It knows the digit grouping, decimal mark, digits and date order of a
+//! few dozen languages and falls back to English for the rest. Mazes say
+//! which language their own text is in, so frontends can lay right-to-left
+//! descriptions out from the right whatever the player's own language.
+
+use std::time::Duration;
+
+/// Which way a language's lines of text run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+/// The order of day, month and year in a written date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+/// A language, and optionally the region it is written in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Locale {
+    /// Lower-case language code, such as `"en"` or `"he"`
+    pub language: String,
+    /// Upper-case region code, such as `"US"`, if given
+    pub region: Option<String>,
+    /// Write the language's own digits, such as Arabic's `٣`, rather than
+    /// `0` to `9`. On by default for languages that have them; frontends
+    /// whose fonts lack them turn it off.
+    pub native_digits: bool,
+}
+
+/// Languages written right to left
+const RIGHT_TO_LEFT: [&str; 8] = ["ar", "dv", "fa", "he", "ps", "sd", "ur", "yi"];
+
+/// Languages that write digits of their own by default
+const NATIVE_DIGITS: [&str; 3] = ["ar", "fa", "ps"];
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            language: "en".to_string(),
+            region: None,
+            native_digits: false,
+        }
+    }
+}
+
+impl Locale {
+    /// Reads a tag such as `"pt-BR"`, `"fr"` or `"de_DE.UTF-8@euro"`.
+    /// `"C"` and `"POSIX"` are English. Returns `None` for anything else
+    /// that does not start with a language code.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let tag = tag.split(['.', '@']).next().unwrap_or_default();
+        if tag == "C" || tag == "POSIX" {
+            return Some(Self::default());
+        }
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next().filter(|code| is_code(code, 2..=3))?;
+        let region = parts.next().filter(|code| is_code(code, 2..=3));
+        let language = language.to_ascii_lowercase();
+        Some(Self {
+            native_digits: NATIVE_DIGITS.contains(&language.as_str()),
+            language,
+            region: region.map(|code| code.to_ascii_uppercase()),
+        })
+    }
+
+    /// The locale the environment asks for, through `LC_ALL`,
+    /// `LC_NUMERIC` or `LANG` in that order, or English
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn direction(&self) -> TextDirection {
+        if RIGHT_TO_LEFT.contains(&self.language.as_str()) {
+            TextDirection::RightToLeft
+        } else {
+            TextDirection::LeftToRight
+        }
+    }
+
+    pub fn is_right_to_left(&self) -> bool {
+        self.direction() == TextDirection::RightToLeft
+    }
+
+    /// A whole number with its digits grouped in threes, such as
+    /// `12,345` in English or `12.345` in German
+    pub fn number(&self, value: impl Into<i64>) -> String {
+        let value = value.into();
+        let digits = value.unsigned_abs().to_string();
+        let mut grouped = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push_str(self.group_separator());
+            }
+            grouped.push(digit);
+        }
+        let sign = if value < 0 { "−" } else { "" };
+        self.digits(&format!("{}{}", sign, grouped))
+    }
+
+    /// A number with `places` digits after the decimal mark
+    pub fn decimal(&self, value: f64, places: usize) -> String {
+        let text = format!("{:.*}", places, value.abs());
+        let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+        let whole = whole.parse::<i64>().unwrap_or_default();
+        let sign = if value < 0.0 && text.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            "−"
+        } else {
+            ""
+        };
+        let mut written = format!("{}{}", sign, self.number(whole));
+        if !fraction.is_empty() {
+            written.push_str(self.decimal_mark());
+            written.push_str(&self.digits(fraction));
+        }
+        written
+    }
+
+    /// A length of time as minutes and seconds, such as `4:05`, with
+    /// hours in front once it runs past an hour
+    pub fn duration(&self, duration: Duration) -> String {
+        let secs = duration.as_secs();
+        let text = if secs >= 3600 {
+            format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        } else {
+            format!("{}:{:02}", secs / 60, secs % 60)
+        };
+        self.digits(&text)
+    }
+
+    /// The date of a moment given in seconds since 1970, in UTC, such as
+    /// `10/14/2026` in the US, `14.10.2026` in Germany and `2026-10-14` in
+    /// Sweden
+    pub fn date(&self, unix_secs: u64) -> String {
+        let (year, month, day) = civil_date(unix_secs);
+        let separator = self.date_separator();
+        let text = match self.date_order() {
+            DateOrder::DayMonthYear => {
+                format!("{:02}{sep}{:02}{sep}{}", day, month, year, sep = separator)
+            }
+            DateOrder::MonthDayYear => {
+                format!("{:02}{sep}{:02}{sep}{}", month, day, year, sep = separator)
+            }
+            DateOrder::YearMonthDay => {
+                format!("{}{sep}{:02}{sep}{:02}", year, month, day, sep = separator)
+            }
+        };
+        self.digits(&text)
+    }
+
+    fn group_separator(&self) -> &'static str {
+        match (self.language.as_str(), self.region.as_deref()) {
+            ("de" | "it", Some("CH")) => "’",
+            ("de" | "es" | "it" | "nl" | "pt" | "tr" | "da" | "id" | "el" | "ro" | "hr", _)
+            | ("sl" | "sr", _) => ".",
+            // No-break space, so a number is never split across lines
+            ("fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" | "uk" | "hu", _)
+            | ("bg" | "lt" | "lv" | "et", _) => "\u{a0}",
+            ("ar" | "fa" | "ps", _) if self.native_digits => "٬",
+            _ => ",",
+        }
+    }
+
+    fn decimal_mark(&self) -> &'static str {
+        match self.group_separator() {
+            "," | "’" => ".",
+            "٬" => "٫",
+            _ => ",",
+        }
+    }
+
+    fn date_order(&self) -> DateOrder {
+        match (self.language.as_str(), self.region.as_deref()) {
+            ("en", None | Some("US")) => DateOrder::MonthDayYear,
+            ("zh" | "ja" | "ko" | "hu" | "lt" | "sv", _) => DateOrder::YearMonthDay,
+            _ => DateOrder::DayMonthYear,
+        }
+    }
+
+    fn date_separator(&self) -> &'static str {
+        match self.language.as_str() {
+            "de" | "ru" | "pl" | "cs" | "sk" | "fi" | "nb" | "no" | "tr" | "uk" | "da" | "ro"
+            | "hu" => ".",
+            "sv" | "lt" | "nl" => "-",
+            _ => "/",
+        }
+    }
+
+    /// Swaps Western digits for the language's own, if it writes them
+    fn digits(&self, text: &str) -> String {
+        let zero = match self.language.as_str() {
+            _ if !self.native_digits => return text.to_string(),
+            "ar" => '٠',
+            "fa" | "ps" => '۰',
+            _ => return text.to_string(),
+        };
+        text.chars()
+            .map(|c| match c.to_digit(10) {
+                Some(d) => char::from_u32(zero as u32 + d).unwrap_or(c),
+                None => c,
+            })
+            .collect()
+    }
+}
+
+fn is_code(code: &str, lengths: std::ops::RangeInclusive<usize>) -> bool {
+    lengths.contains(&code.len()) && code.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Year, month and day of a moment in seconds since 1970, in UTC
+pub fn civil_date(unix_secs: u64) -> (i64, i64, i64) {
+    let days = (unix_secs / 86_400) as i64;
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
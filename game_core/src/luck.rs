@@ -0,0 +1,163 @@
+//! Chance in play: random exits, hazards that may not strike, and the
+//! player's luck and rerolls.
+//!
+//! An exit can list outcomes to draw its destination from, by weight,
+//! and a hazard can strike only some of the time. Luck tips each draw
+//! towards outcomes marked lucky and away from hazards striking. A
+//! reroll token takes back the last random exit and draws again.
+//!
+//! Every draw comes from the seeded generator kept in the play state,
+//! and rerolling is an [`Action`](crate::action::Action), so recorded
+//! games replay the same.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hazard::Hazard;
+use crate::rng::Rng;
+use crate::{Exit, GameState, PlayState};
+
+/// How much each point of luck adds to lucky outcomes' weight, and
+/// takes off a hazard's chance, in percent
+pub const LUCK_PERCENT: u32 = 10;
+
+/// The most luck can add, in percent, however much the player has
+pub const MAX_LUCK_PERCENT: u64 = 100;
+
+/// A place a random exit may lead.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub destination: String,
+    /// Relative chance of this outcome
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// Made likelier by the player's luck
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub lucky: bool,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// The game from just before a random exit, for rerolling it.
+#[derive(Clone)]
+pub(crate) struct Redraw {
+    before: Box<PlayState>,
+    exit: usize,
+}
+
+impl Exit {
+    /// Every room the exit may lead to: its destination, then each
+    /// outcome's
+    pub fn destinations(&self) -> impl Iterator<Item = &str> + Clone {
+        std::iter::once(self.destination.as_str()).chain(
+            self.outcomes
+                .iter()
+                .map(|outcome| outcome.destination.as_str()),
+        )
+    }
+}
+
+impl GameState {
+    /// Seeds the game's generator, so each new game draws differently
+    pub fn reseed(&mut self, seed: u64) {
+        self.play.rng = seed;
+    }
+
+    /// Whether the last move was a random exit the player can reroll
+    pub fn can_reroll(&self) -> bool {
+        self.play.redraw.is_some() && self.play.rerolls > 0
+    }
+
+    /// Whether the maze has anything left to chance, so frontends know
+    /// whether luck and rerolls are worth showing
+    pub fn uses_luck(&self) -> bool {
+        self.maze.rooms.iter().any(|room| {
+            let hazards = room
+                .hazard
+                .iter()
+                .chain(room.exits.iter().flat_map(|e| &e.hazard));
+            room.exits.iter().any(|exit| !exit.outcomes.is_empty())
+                || hazards
+                    .clone()
+                    .any(|hazard| hazard.chance_percent.is_some())
+        })
+    }
+
+    /// Draws where the random exit at `index` leads, or `None` if it has
+    /// no outcomes
+    pub(crate) fn draw_destination(&mut self, index: usize) -> Option<String> {
+        let outcomes = self.current_room().exits.get(index)?.outcomes.clone();
+        let luck = self.luck_percent();
+        let weights: Vec<u64> = outcomes
+            .iter()
+            .map(|outcome| {
+                let bonus = if outcome.lucky { luck } else { 0 };
+                u64::from(outcome.weight) * (100 + bonus)
+            })
+            .collect();
+        let total: u64 = weights.iter().sum();
+        if total == 0 {
+            return outcomes.first().map(|outcome| outcome.destination.clone());
+        }
+
+        let mut roll = self.roll() % total;
+        for (outcome, weight) in outcomes.iter().zip(&weights) {
+            if roll < *weight {
+                return Some(outcome.destination.clone());
+            }
+            roll -= weight;
+        }
+        None
+    }
+
+    /// What the player's luck adds to lucky draws, in percent
+    fn luck_percent(&self) -> u64 {
+        let luck = u64::from(self.maze.player.luck) * u64::from(LUCK_PERCENT);
+        luck.min(MAX_LUCK_PERCENT)
+    }
+
+    /// Whether a hazard strikes this time, drawing if it is left to
+    /// chance
+    pub(crate) fn hazard_strikes(&mut self, hazard: &Hazard) -> bool {
+        let Some(chance) = hazard.chance_percent else {
+            return true;
+        };
+        let luck = self.luck_percent();
+        // In hundredths of a percent, so luck shrinks the chance smoothly
+        let odds = u64::from(chance.min(100)) * 100 * 100 / (100 + luck);
+        self.roll() % 10_000 < odds
+    }
+
+    /// Remembers the game from before a random exit, so it can be rerolled
+    pub(crate) fn allow_reroll(&mut self, mut before: PlayState, exit: usize) {
+        before.redraw = None;
+        self.play.redraw = Some(Redraw {
+            before: Box::new(before),
+            exit,
+        });
+    }
+
+    /// Spends a reroll token to take back the last random exit and draw
+    /// again. Time and hints used are not taken back.
+    pub(crate) fn reroll(&mut self, redraw: Redraw) -> bool {
+        if self.play.rerolls == 0 {
+            return false;
+        }
+        let after = std::mem::replace(&mut self.play, *redraw.before);
+        self.play.rng = after.rng;
+        self.play.rerolls = after.rerolls - 1;
+        self.play.elapsed_ms = after.elapsed_ms;
+        self.play.hints_used = after.hints_used;
+        self.choose_exit(redraw.exit);
+        true
+    }
+
+    /// The next number from the game's generator
+    fn roll(&mut self) -> u64 {
+        let mut rng = Rng::new(self.play.rng);
+        let value = rng.next_u64();
+        self.play.rng = rng.state();
+        value
+    }
+}
@@ -0,0 +1,78 @@
+//! Fuzzy matching of typed text against exit labels.
+//!
+//! Shared by [`GameState::choose_exit_by_label`] and frontends' filter
+//! boxes so both agree on what a query means.
+
+use crate::{GameState, Room};
+
+/// Scores how well `query` matches `label`, ignoring case. Higher is
+/// better; `None` means no match. An empty query matches everything.
+///
+/// Exact matches beat prefixes, prefixes beat substrings, and substrings
+/// beat labels merely containing the query's characters in order.
+pub fn match_score(query: &str, label: &str) -> Option<u32> {
+    let query = query.trim().to_lowercase();
+    let label = label.to_lowercase();
+
+    if query.is_empty() {
+        return Some(0);
+    }
+    if label == query {
+        return Some(400);
+    }
+    if label.starts_with(&query) {
+        return Some(300);
+    }
+    if let Some(at) = label.find(&query) {
+        // Earlier matches, and matches at a word start, rank higher
+        let word_start = label[..at].ends_with(|c: char| !c.is_alphanumeric());
+        return Some(200 + u32::from(word_start) * 50 - (at.min(49) as u32));
+    }
+
+    // Subsequence match: every query character appears in order
+    let mut chars = label.chars();
+    let mut gaps = 0u32;
+    for q in query.chars() {
+        loop {
+            match chars.next() {
+                Some(c) if c == q => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+    Some(100u32.saturating_sub(gaps.min(99)))
+}
+
+impl Room {
+    /// Indices of exits matching `query`, best match first
+    pub fn matching_exits(&self, query: &str) -> Vec<usize> {
+        let mut scored: Vec<(u32, usize)> = self
+            .exits
+            .iter()
+            .enumerate()
+            .filter_map(|(i, exit)| match_score(query, &exit.label).map(|score| (score, i)))
+            .collect();
+        // Stable sort keeps room order among equal scores
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        scored.into_iter().map(|(_, i)| i).collect()
+    }
+}
+
+impl GameState {
+    /// Takes the open exit whose label best matches `query`.
+    /// Returns false if no open exit matches or the player cannot act.
+    pub fn choose_exit_by_label(&mut self, query: &str) -> bool {
+        if query.trim().is_empty() || !self.can_act() {
+            return false;
+        }
+        let matches = self.current_room().matching_exits(query);
+        match matches.into_iter().find(|&i| self.exit_available(i)) {
+            Some(index) => {
+                self.choose_exit(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
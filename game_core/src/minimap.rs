@@ -0,0 +1,151 @@
+//! A map of the rooms the player has visited, laid out for drawing.
+//!
+//! Rooms go where the author placed them when every visited room has a
+//! position. Otherwise they are laid out in layers by how many steps
+//! they are from the start, through visited rooms, so the map grows
+//! outwards as the player explores, rightwards or, in mazes written in a
+//! right-to-left language, leftwards.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::GameState;
+use crate::locale::TextDirection;
+
+/// A visited room on the map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapRoom<'a> {
+    pub id: &'a str,
+    /// Where to draw the room, between 0 and 1 on each axis
+    pub x: f32,
+    pub y: f32,
+    pub current: bool,
+}
+
+/// The visited part of the maze: its rooms, and the open exits between
+/// them as pairs of indices into `rooms`, each pair listed once.
+#[derive(Clone, Debug, Default)]
+pub struct Minimap<'a> {
+    pub rooms: Vec<MapRoom<'a>>,
+    pub links: Vec<(usize, usize)>,
+}
+
+impl GameState {
+    pub fn minimap(&self) -> Minimap<'_> {
+        let visited: Vec<&str> = self.play.visited.iter().map(String::as_str).collect();
+        let index: HashMap<&str, usize> =
+            visited.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        // Exits between visited rooms, either way round counted once
+        let mut links = Vec::new();
+        let mut neighbours = vec![Vec::new(); visited.len()];
+        for (from, id) in visited.iter().enumerate() {
+            let Some(room) = self.get_room(id) else {
+                continue;
+            };
+            let open = room
+                .exits
+                .iter()
+                .filter(|exit| exit.is_open(&self.play.flags));
+            for exit in open {
+                let Some(&to) = index.get(exit.destination.as_str()) else {
+                    continue;
+                };
+                neighbours[from].push(to);
+                let link = (from.min(to), from.max(to));
+                if from != to && !links.contains(&link) {
+                    links.push(link);
+                }
+            }
+        }
+
+        let positions: Option<Vec<_>> = visited
+            .iter()
+            .map(|id| self.get_room(id).and_then(|room| room.position))
+            .collect();
+        let points = match positions {
+            Some(positions) => normalise(positions.iter().map(|p| (p.x, p.y)).collect()),
+            None => {
+                let mut points = normalise(layers(&neighbours));
+                if self.maze.text_direction() == TextDirection::RightToLeft {
+                    for point in &mut points {
+                        point.0 = 1.0 - point.0;
+                    }
+                }
+                points
+            }
+        };
+
+        let rooms = visited
+            .iter()
+            .zip(points)
+            .map(|(id, (x, y))| MapRoom {
+                id,
+                x,
+                y,
+                current: *id == self.play.current_room,
+            })
+            .collect();
+        Minimap { rooms, links }
+    }
+}
+
+/// Places rooms in columns by steps from the first room, in the order
+/// visited down each centred column. Rooms it cannot reach go in a last
+/// column.
+fn layers(neighbours: &[Vec<usize>]) -> Vec<(f32, f32)> {
+    let mut depth = vec![None; neighbours.len()];
+    let mut queue = VecDeque::new();
+    if !neighbours.is_empty() {
+        depth[0] = Some(0);
+        queue.push_back(0);
+    }
+    while let Some(room) = queue.pop_front() {
+        let next = depth[room].map(|d: usize| d + 1);
+        for &to in &neighbours[room] {
+            if depth[to].is_none() {
+                depth[to] = next;
+                queue.push_back(to);
+            }
+        }
+    }
+
+    let unreached = depth.iter().flatten().max().map_or(0, |d| d + 1);
+    let columns: Vec<usize> = depth.iter().map(|d| d.unwrap_or(unreached)).collect();
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    for column in &columns {
+        *sizes.entry(*column).or_default() += 1;
+    }
+    let mut rows: HashMap<usize, usize> = HashMap::new();
+    columns
+        .iter()
+        .map(|column| {
+            let row = rows.entry(*column).or_default();
+            let y = *row as f32 - (sizes[column] - 1) as f32 / 2.0;
+            *row += 1;
+            (*column as f32, y)
+        })
+        .collect()
+}
+
+/// Scales points into 0 to 1 on each axis, centring any axis they do
+/// not spread along
+fn normalise(points: Vec<(f32, f32)>) -> Vec<(f32, f32)> {
+    let range = |values: Vec<f32>| {
+        let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        (min, max - min)
+    };
+    let (min_x, width) = range(points.iter().map(|p| p.0).collect());
+    let (min_y, height) = range(points.iter().map(|p| p.1).collect());
+    let scale = |value: f32, min: f32, span: f32| {
+        if span > 0.0 {
+            (value - min) / span
+        } else {
+            0.5
+        }
+    };
+    points
+        .into_iter()
+        .map(|(x, y)| (scale(x, min_x, width), scale(y, min_y, height)))
+        .collect()
+}
@@ -0,0 +1,103 @@
+//! Maze music in layers that follow how tense the game is.
+//!
+//! A maze can provide a calm, a tense and a chase track, played together
+//! in sync with only the fitting one audible. The game is tense with an
+//! enemy one room away or the clock running low, and a chase while
+//! fighting. [`GameState::music_levels`] gives each layer's level, to
+//! feed an [`ambience::Mixer`](crate::ambience::Mixer) for fading.
+//!
+//! Core only works out the levels; frontends play the tracks.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+
+/// Time left from which a timed maze turns tense
+pub const TENSE_TIME_LEFT: Duration = Duration::from_secs(30);
+
+/// Music files for each intensity, relative to the maze file. Missing
+/// layers fall back to the next calmer one.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Music {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tense: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chase: Option<String>,
+}
+
+/// How intense the moment is, calmest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Intensity {
+    Calm,
+    Tense,
+    Chase,
+}
+
+impl Intensity {
+    pub const ALL: [Intensity; 3] = [Intensity::Calm, Intensity::Tense, Intensity::Chase];
+
+    /// Layer name, as used for mixer levels
+    pub fn name(self) -> &'static str {
+        match self {
+            Intensity::Calm => "calm",
+            Intensity::Tense => "tense",
+            Intensity::Chase => "chase",
+        }
+    }
+}
+
+impl Music {
+    /// The file for a layer, if the maze has one
+    pub fn track(&self, intensity: Intensity) -> Option<&str> {
+        match intensity {
+            Intensity::Calm => self.calm.as_deref(),
+            Intensity::Tense => self.tense.as_deref(),
+            Intensity::Chase => self.chase.as_deref(),
+        }
+    }
+}
+
+impl GameState {
+    /// How intense the game is right now
+    pub fn intensity(&self) -> Intensity {
+        if self.in_combat() {
+            return Intensity::Chase;
+        }
+        let enemy_near = self.available_exits().into_iter().any(|i| {
+            let exit = &self.current_room().exits[i];
+            self.get_room(&exit.destination)
+                .and_then(|room| room.enemy.as_ref())
+                .is_some_and(|enemy| !self.play.defeated.contains(&enemy.id))
+        });
+        let time_low = self.time_left().is_some_and(|left| left <= TENSE_TIME_LEFT);
+        if self.play.phase.can_move() && (enemy_near || time_low) {
+            Intensity::Tense
+        } else {
+            Intensity::Calm
+        }
+    }
+
+    /// The level each music layer should play at, by layer name: full
+    /// for the layer that fits, or the nearest calmer one the maze has.
+    /// Empty for mazes without music.
+    pub fn music_levels(&self) -> BTreeMap<&'static str, f32> {
+        let Some(music) = &self.maze.music else {
+            return BTreeMap::new();
+        };
+        let intensity = self.intensity();
+        let playing = Intensity::ALL
+            .into_iter()
+            .rev()
+            .filter(|&layer| layer <= intensity)
+            .find(|&layer| music.track(layer).is_some());
+        playing
+            .map(|layer| (layer.name(), 1.0))
+            .into_iter()
+            .collect()
+    }
+}
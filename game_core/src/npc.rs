@@ -0,0 +1,151 @@
+//! Characters in rooms the player can talk to.
+//!
+//! Each character has a dialogue tree: lines they say, each with replies
+//! the player can pick. A reply can lead to another line or end the
+//! conversation, and can set [flags](crate::flags) and hand over items
+//! on the way. Conversations end when the player leaves the room.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::flags::Condition;
+
+/// A character standing in a room.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Npc {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// What they can say; talking starts at the first line
+    pub dialogue: Vec<DialogueLine>,
+}
+
+/// Something a character says, and how the player can answer.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DialogueLine {
+    pub id: String,
+    pub text: String,
+    /// With none, the player can only say goodbye
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<Reply>,
+}
+
+/// An answer the player can give.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Reply {
+    pub label: String,
+    /// Line the character answers with; the conversation ends if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    /// Flags that must be set for the reply to be offered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+    /// Flags turned on or off by giving this reply
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub set_flags: BTreeMap<String, bool>,
+    /// Items the character hands over
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub give_items: Vec<String>,
+}
+
+/// Who the player is talking to and the line they are at.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Conversation {
+    pub npc: String,
+    pub line: String,
+}
+
+impl Npc {
+    pub fn line(&self, id: &str) -> Option<&DialogueLine> {
+        self.dialogue.iter().find(|line| line.id == id)
+    }
+}
+
+impl GameState {
+    /// Characters in the current room
+    pub fn npcs_here(&self) -> &[Npc] {
+        &self.current_room().npcs
+    }
+
+    /// The character being talked to and their current line, if any
+    pub fn conversation(&self) -> Option<(&Npc, &DialogueLine)> {
+        let conversation = self.play.conversation.as_ref()?;
+        let npc = self
+            .npcs_here()
+            .iter()
+            .find(|npc| npc.id == conversation.npc)?;
+        Some((npc, npc.line(&conversation.line)?))
+    }
+
+    /// Indices of the current line's replies the flags allow
+    pub fn open_replies(&self) -> Vec<usize> {
+        let Some((_, line)) = self.conversation() else {
+            return Vec::new();
+        };
+        line.replies
+            .iter()
+            .enumerate()
+            .filter(|(_, reply)| {
+                reply
+                    .when
+                    .as_ref()
+                    .is_none_or(|when| when.holds(&self.play.flags))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Starts talking to a character in the current room.
+    /// Returns false if they are not here or have nothing to say.
+    pub fn talk_to(&mut self, id: &str) -> bool {
+        if !self.can_act() {
+            return false;
+        }
+        let first = self
+            .npcs_here()
+            .iter()
+            .find(|npc| npc.id == id)
+            .and_then(|npc| npc.dialogue.first());
+        let Some(first) = first else {
+            return false;
+        };
+        self.play.conversation = Some(Conversation {
+            npc: id.to_string(),
+            line: first.id.clone(),
+        });
+        true
+    }
+
+    /// Gives the reply at `index` of the current line: its flags and
+    /// items take effect and the conversation moves on or ends.
+    /// Returns false if there is no such reply to give now.
+    pub fn reply(&mut self, index: usize) -> bool {
+        let Some((_, line)) = self.conversation() else {
+            return false;
+        };
+        if !self.open_replies().contains(&index) {
+            return false;
+        }
+        let reply = line.replies[index].clone();
+
+        for (flag, &on) in &reply.set_flags {
+            self.set_flag(flag, on);
+        }
+        for item in &reply.give_items {
+            self.give_item(item);
+        }
+        match (&mut self.play.conversation, reply.next) {
+            (Some(conversation), Some(next)) => conversation.line = next,
+            _ => self.play.conversation = None,
+        }
+        true
+    }
+
+    /// Walks away from the conversation. Returns false if there was none.
+    pub fn end_conversation(&mut self) -> bool {
+        self.play.conversation.take().is_some()
+    }
+}
@@ -0,0 +1,295 @@
+//! Recorded games that can be played back and checked step by step.
+//!
+//! A replay is the seed the game drew from, the list of actions taken,
+//! and a [`PlayState::state_hash`] every [`CHECKPOINT_INTERVAL`] steps.
+//! Playing it back recomputes the hashes, so if anything makes playback
+//! drift from the original game the first diverging checkpoint is
+//! reported.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::{GameState, Phase, PlayState};
+
+/// Steps between recorded state hashes
+pub const CHECKPOINT_INTERVAL: usize = 16;
+
+/// A recorded game.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Replay {
+    /// The game's generator when recording started, see
+    /// [`GameState::reseed`]
+    #[serde(default)]
+    pub seed: u64,
+    pub actions: Vec<Action>,
+    /// `(step, hash)` after that many actions, starting with step 0
+    pub checkpoints: Vec<(usize, u64)>,
+}
+
+/// Where playback stopped matching the recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Desync {
+    /// The action at this step was not allowed on playback
+    Rejected { step: usize },
+    /// The state after `step` actions hashed differently
+    HashMismatch {
+        step: usize,
+        expected: u64,
+        found: u64,
+    },
+}
+
+impl fmt::Display for Desync {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Rejected { step } => write!(f, "action {} was not allowed on playback", step + 1),
+            Self::HashMismatch {
+                step,
+                expected,
+                found,
+            } => write!(
+                f,
+                "state after step {} differs: expected {:016x}, found {:016x}",
+                step, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Desync {}
+
+impl Replay {
+    /// Starts recording a game from its current state
+    pub fn new(state: &GameState) -> Self {
+        Self {
+            seed: state.play.rng,
+            actions: Vec::new(),
+            checkpoints: vec![(0, state.play.state_hash())],
+        }
+    }
+
+    /// Records an action that was just applied to `state`
+    pub fn record(&mut self, state: &GameState, action: Action) {
+        self.actions.push(action);
+        let step = self.actions.len();
+        if step.is_multiple_of(CHECKPOINT_INTERVAL) {
+            self.checkpoints.push((step, state.play.state_hash()));
+        }
+    }
+
+    /// Plays the replay on `state`, which should be at the recording's
+    /// start, checking every checkpoint along the way
+    pub fn play(&self, state: &mut GameState) -> Result<(), Desync> {
+        let mut checkpoints = self.checkpoints.iter().peekable();
+        for step in 0..=self.actions.len() {
+            if let Some(&&(at, expected)) = checkpoints.peek()
+                && at == step
+            {
+                let found = state.play.state_hash();
+                if found != expected {
+                    return Err(Desync::HashMismatch {
+                        step,
+                        expected,
+                        found,
+                    });
+                }
+                checkpoints.next();
+            }
+            if let Some(action) = self.actions.get(step)
+                && !state.apply(action)
+            {
+                return Err(Desync::Rejected { step });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PlayState {
+    /// A hash of everything that affects play, stable across runs,
+    /// platforms and versions of Rust. Bookmarks are left out as they
+    /// change nothing in the game, and time played and hints as
+    /// recordings hold actions, not clock ticks or hints.
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = Fnv::new();
+        hash.write_str(&self.current_room);
+        match &self.phase {
+            Phase::Exploring => hash.write_u64(0),
+            Phase::Finished { ending } => {
+                hash.write_u64(1);
+                hash.write_str(ending);
+            }
+            Phase::FreeRoam { ending } => {
+                hash.write_u64(2);
+                hash.write_str(ending);
+            }
+            Phase::GameOver { cause } => {
+                hash.write_u64(3);
+                hash.write_str(cause);
+            }
+        }
+        for list in [&self.visited, &self.inventory] {
+            hash.write_u64(list.len() as u64);
+            for id in list {
+                hash.write_str(id);
+            }
+        }
+        hash.write_u64(self.dropped_items.len() as u64);
+        for (item, room) in &self.dropped_items {
+            hash.write_str(item);
+            hash.write_str(room);
+        }
+        hash.write_u64(self.flags.len() as u64);
+        for (flag, &value) in &self.flags {
+            hash.write_str(flag);
+            hash.write_u64(u64::from(value));
+        }
+        match &self.conversation {
+            Some(conversation) => {
+                hash.write_u64(1);
+                hash.write_str(&conversation.npc);
+                hash.write_str(&conversation.line);
+            }
+            None => hash.write_u64(0),
+        }
+        hash.write_u64(u64::from(self.health));
+        hash.write_u64(u64::from(self.stamina));
+        hash.write_u64(u64::from(self.steps));
+        hash.write_u64(u64::from(self.turns));
+        hash.write_u64(self.exit_uses.len() as u64);
+        for (room, uses) in &self.exit_uses {
+            hash.write_str(room);
+            hash.write_u64(uses.len() as u64);
+            for used in uses {
+                hash.write_u64(used.exit as u64);
+                hash.write_u64(u64::from(used.turn));
+            }
+        }
+        hash.write_u64(self.rng);
+        hash.write_u64(u64::from(self.rerolls));
+        match &self.combat {
+            Some(combat) => {
+                hash.write_u64(1);
+                hash.write_str(&combat.enemy);
+                hash.write_u64(u64::from(combat.enemy_hp));
+                hash.write_str(&combat.fled_to);
+            }
+            None => hash.write_u64(0),
+        }
+        hash.write_u64(self.defeated.len() as u64);
+        for id in &self.defeated {
+            hash.write_str(id);
+        }
+        match &self.cutscene {
+            Some(playback) => {
+                hash.write_u64(1);
+                hash.write_str(&playback.room);
+                hash.write_u64(playback.beat as u64);
+            }
+            None => hash.write_u64(0),
+        }
+        hash.0
+    }
+}
+
+/// 64-bit FNV-1a, which unlike std's hashers is fixed by its spec
+pub(crate) struct Fnv(pub(crate) u64);
+
+impl Fnv {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Length first, so "ab" + "c" and "a" + "bc" hash differently
+    fn write_str(&mut self, s: &str) {
+        self.write_u64(s.len() as u64);
+        self.write(s.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+
+    fn game() -> GameState {
+        let file: MazeFile = serde_json::from_str(
+            r#"{"rooms": [
+                {"id": "a", "description": "A", "exits": [
+                    {"label": "Across", "destination": "b"}
+                ]},
+                {"id": "b", "description": "B", "exits": [
+                    {"label": "Back", "destination": "a"},
+                    {"label": "Gamble", "outcomes": [
+                        {"destination": "a"}, {"destination": "b"}
+                    ], "destination": "a"}
+                ]},
+                {"id": "end", "description": "End", "exits": [], "is_end": true}
+            ]}"#,
+        )
+        .unwrap();
+        let mut state = GameState::from_maze(file).unwrap();
+        state.reseed(42);
+        state
+    }
+
+    /// Plays 20 steps, taking the random exit whenever it is there
+    fn record() -> (Replay, u64) {
+        let mut state = game();
+        let mut replay = Replay::new(&state);
+        for _ in 0..20 {
+            let action = Action::ChooseExit(state.current_room().exits.len() - 1);
+            assert!(state.apply(&action));
+            replay.record(&state, action);
+        }
+        (replay, state.play.state_hash())
+    }
+
+    #[test]
+    fn playback_reproduces_the_final_state() {
+        let (replay, final_hash) = record();
+        assert_eq!(replay.checkpoints.len(), 2);
+
+        let mut state = game();
+        replay.play(&mut state).unwrap();
+        assert_eq!(state.play.state_hash(), final_hash);
+    }
+
+    #[test]
+    fn reports_the_checkpoint_that_differs() {
+        let (mut replay, _) = record();
+        replay.checkpoints[1].1 ^= 1;
+
+        let error = replay.play(&mut game()).unwrap_err();
+        assert!(matches!(
+            error,
+            Desync::HashMismatch {
+                step: CHECKPOINT_INTERVAL,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn reports_a_different_seed() {
+        let (mut replay, _) = record();
+        replay.seed += 1;
+        let mut state = game();
+        state.reseed(replay.seed);
+
+        assert!(replay.play(&mut state).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Saved games: a game state tagged with the maze it belongs to.
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::storage::Storage;
+
+/// Current save format version
+pub const SAVE_VERSION: u32 = 1;
+
+/// A game in progress, as written to disk.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SaveGame {
+    pub version: u32,
+    /// Name of the maze the game was played on
+    pub maze: String,
+    pub state: GameState,
+}
+
+impl SaveGame {
+    pub fn new(maze: &str, state: GameState) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            maze: maze.to_string(),
+            state,
+        }
+    }
+
+    /// Reads the save under `key`, rejecting ones from a newer version of
+    /// the game or whose maze is broken
+    pub fn load(storage: &dyn Storage, key: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let text = storage.read(key)?.ok_or("there is no saved game")?;
+        let save: Self = serde_json::from_str(&text)?;
+        if save.version > SAVE_VERSION {
+            return Err(format!("save version {} is newer than this game", save.version).into());
+        }
+        save.state.check()?;
+        Ok(save)
+    }
+
+    /// Writes the save under `key`, replacing any before it whole
+    pub fn write(
+        &self,
+        storage: &dyn Storage,
+        key: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        storage.write(key, &serde_json::to_string(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn round_trips_exits_closed_after_use() {
+        let file: MazeFile = serde_json::from_str(
+            r#"{"rooms": [
+                {"id": "a", "description": "A", "exits": [
+                    {"label": "Drawbridge", "destination": "b", "cooldown_turns": 3},
+                    {"label": "Trapdoor", "destination": "b", "once": true}
+                ]},
+                {"id": "b", "description": "B", "exits": [
+                    {"label": "Back", "destination": "a"}
+                ]},
+                {"id": "c", "description": "C", "exits": [], "is_end": true}
+            ]}"#,
+        )
+        .unwrap();
+        let mut state = GameState::from_maze(file).unwrap();
+        state.choose_exit(0);
+        state.choose_exit(0);
+        state.choose_exit(1);
+        state.choose_exit(0);
+
+        let storage = MemoryStorage::default();
+        SaveGame::new("test", state.clone())
+            .write(&storage, "save.json")
+            .unwrap();
+        let loaded = SaveGame::load(&storage, "save.json").unwrap().state;
+
+        assert_eq!(loaded.play.exit_uses, state.play.exit_uses);
+        assert_eq!(loaded.play.turns, state.play.turns);
+        for index in 0..2 {
+            assert_eq!(loaded.exit_closed(index), state.exit_closed(index));
+        }
+        assert!(loaded.exit_closed(1).is_some());
+    }
+}
@@ -0,0 +1,96 @@
+//! Scoring a finished game against the maze's par.
+//!
+//! Par is the fewest steps from the start room to the ending reached, as
+//! the maze stands when a game begins.
+//! A game starts from a fixed number of points and loses some for each
+//! step over par and each hint used, down to zero.
+
+use crate::{GameState, Phase};
+
+/// Points a game at par without hints scores
+pub const BASE_POINTS: u32 = 1000;
+
+/// Points lost for each step over par
+pub const STEP_PENALTY: u32 = 20;
+
+/// Points lost for each hint used
+pub const HINT_PENALTY: u32 = 100;
+
+/// How a finished game's points were worked out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
+    pub steps: u32,
+    /// Fewest steps the ending can be reached in
+    pub par: u32,
+    pub hints: u32,
+    /// Points lost to steps over par
+    pub step_penalty: u32,
+    /// Points lost to hints
+    pub hint_penalty: u32,
+    pub points: u32,
+}
+
+impl GameState {
+    /// The score for a finished game, or `None` if it is not finished
+    pub fn score(&self) -> Option<Score> {
+        let ending = match &self.play.phase {
+            Phase::Finished { ending } | Phase::FreeRoam { ending } => ending,
+            Phase::Exploring | Phase::GameOver { .. } => return None,
+        };
+        // Par is planned from a fresh game, so exits used up, cooling down
+        // or opened by flags along the way do not move it
+        let fresh = GameState::start(self.maze.clone());
+        let par = fresh
+            .shortest_path(&fresh.play.current_room, ending)
+            .map_or(self.play.steps, |path| path.len() as u32);
+
+        let (steps, hints) = (self.play.steps, self.play.hints_used);
+        let step_penalty = steps.saturating_sub(par).saturating_mul(STEP_PENALTY);
+        let hint_penalty = hints.saturating_mul(HINT_PENALTY);
+        Some(Score {
+            steps,
+            par,
+            hints,
+            step_penalty,
+            hint_penalty,
+            points: BASE_POINTS.saturating_sub(step_penalty.saturating_add(hint_penalty)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+
+    #[test]
+    fn par_ignores_exits_used_up_on_the_way() {
+        let file: MazeFile = serde_json::from_str(
+            r#"{"rooms": [
+                {"id": "a", "description": "A", "exits": [
+                    {"label": "Shortcut", "destination": "b", "once": true},
+                    {"label": "Long way", "destination": "c"}
+                ]},
+                {"id": "b", "description": "B", "exits": [
+                    {"label": "Back", "destination": "a"},
+                    {"label": "Out", "destination": "end"}
+                ]},
+                {"id": "c", "description": "C", "exits": [
+                    {"label": "On", "destination": "b"}
+                ]},
+                {"id": "end", "description": "End", "exits": [], "is_end": true}
+            ]}"#,
+        )
+        .unwrap();
+        let mut state = GameState::from_maze(file).unwrap();
+        state.choose_exit(0);
+        state.choose_exit(0);
+        state.choose_exit(1);
+        state.choose_exit(0);
+        state.choose_exit(1);
+
+        let score = state.score().unwrap();
+        assert_eq!(score.par, 2);
+        assert_eq!(score.steps, 5);
+    }
+}
@@ -0,0 +1,77 @@
+//! Scripts that mazes attach to rooms and items.
+//!
+//! A room's `on_enter` and `on_exit` and an item's `on_take` hold small
+//! [Rhai](https://rhai.rs) scripts, run when the player does that. They
+//! can use:
+//!
+//! - `print(text)` to tell the player something
+//! - `flag(name)` and `set_flag(name, on)` for the game's [flags](crate::flags)
+//! - `has_item(id)` and `give_item(id)` for the inventory
+//!
+//! Scripts only run in builds with the `scripting` feature, and not
+//! during free roam. Other builds play the maze without them.
+
+use crate::GameState;
+
+/// Work a script may do before it is stopped, so a stray loop cannot
+/// hang the game
+#[cfg(feature = "scripting")]
+const MAX_OPERATIONS: u64 = 100_000;
+
+impl GameState {
+    /// Text scripts printed since the last call, oldest first
+    pub fn take_messages(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.messages)
+    }
+
+    /// Runs a script against this game. Errors are shown to the player
+    /// like printed text, naming `context`, so authors see them.
+    #[cfg(feature = "scripting")]
+    pub(crate) fn run_script(&mut self, script: &str, context: &str) {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        if matches!(self.play.phase, crate::Phase::FreeRoam { .. }) {
+            return;
+        }
+        let play = Rc::new(RefCell::new(self.play.clone()));
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = rhai::Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+
+        let printed = output.clone();
+        engine.on_print(move |text| printed.borrow_mut().push(text.to_string()));
+        let state = play.clone();
+        engine.register_fn("flag", move |name: &str| {
+            state.borrow().flags.get(name).copied().unwrap_or(false)
+        });
+        let state = play.clone();
+        engine.register_fn("set_flag", move |name: &str, on: bool| {
+            state.borrow_mut().flags.insert(name.to_string(), on);
+        });
+        let state = play.clone();
+        engine.register_fn("has_item", move |id: &str| {
+            state.borrow().inventory.iter().any(|carried| carried == id)
+        });
+        // Cloning the table only shares its rooms
+        let (state, rooms) = (play.clone(), self.maze.rooms.clone());
+        engine.register_fn("give_item", move |id: &str| {
+            let mut play = state.borrow_mut();
+            if rooms.item(id).is_some() && !play.inventory.iter().any(|carried| carried == id) {
+                play.dropped_items.remove(id);
+                play.inventory.push(id.to_string());
+            }
+        });
+
+        let result = engine.run(script);
+        self.play = play.borrow().clone();
+        self.messages.append(&mut output.borrow_mut());
+        if let Err(e) = result {
+            self.messages
+                .push(format!("⚠ Script error in {}: {}", context, e));
+        }
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    pub(crate) fn run_script(&mut self, _script: &str, _context: &str) {}
+}
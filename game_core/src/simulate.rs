@@ -0,0 +1,171 @@
+//! Headless batch simulation of playthroughs.
+//!
+//! Runs many automated games against a maze and summarises how they went,
+//! so authors can judge difficulty without playing every branch themselves.
+
+use std::collections::HashSet;
+
+use crate::GameState;
+use crate::rng::Rng;
+
+/// Decides which exit a simulated player takes.
+pub trait Policy {
+    /// Picks an exit index in the current room, or `None` to give up
+    fn choose(&mut self, state: &GameState, rng: &mut Rng) -> Option<usize>;
+
+    /// Called before every run so stateful policies can start fresh
+    fn reset(&mut self) {}
+}
+
+/// Picks a uniformly random exit each turn.
+#[derive(Default)]
+pub struct RandomWalk;
+
+impl Policy for RandomWalk {
+    fn choose(&mut self, state: &GameState, rng: &mut Rng) -> Option<usize> {
+        let exits = state.available_exits();
+        if exits.is_empty() {
+            return None;
+        }
+        Some(exits[rng.below(exits.len())])
+    }
+}
+
+/// Prefers exits leading to rooms not yet seen in this run, falling back
+/// to a random exit when everything nearby has been visited.
+#[derive(Default)]
+pub struct PreferUnvisited {
+    visited: HashSet<String>,
+}
+
+impl Policy for PreferUnvisited {
+    fn choose(&mut self, state: &GameState, rng: &mut Rng) -> Option<usize> {
+        let room = state.current_room();
+        self.visited.insert(room.id.clone());
+        let exits = state.available_exits();
+        if exits.is_empty() {
+            return None;
+        }
+
+        let fresh: Vec<usize> = exits
+            .iter()
+            .copied()
+            .filter(|&i| !self.visited.contains(&room.exits[i].destination))
+            .collect();
+
+        if fresh.is_empty() {
+            Some(exits[rng.below(exits.len())])
+        } else {
+            Some(fresh[rng.below(fresh.len())])
+        }
+    }
+
+    fn reset(&mut self) {
+        self.visited.clear();
+    }
+}
+
+/// Parameters for a batch of simulated playthroughs.
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    /// Number of playthroughs to run
+    pub runs: usize,
+    /// Moves after which a run is counted as abandoned
+    pub max_moves: usize,
+    /// Seed for the random number generator
+    pub seed: u64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            runs: 1000,
+            max_moves: 1000,
+            seed: 0,
+        }
+    }
+}
+
+/// Summary of a batch of simulated playthroughs.
+#[derive(Clone, Debug, Default)]
+pub struct SimulationReport {
+    pub runs: usize,
+    pub completed: usize,
+    /// Total moves across completed runs only
+    pub moves_to_finish: usize,
+    /// Rooms no run ever entered, in maze order
+    pub unreached_rooms: Vec<String>,
+}
+
+impl SimulationReport {
+    /// Fraction of runs that reached an end room
+    pub fn completion_rate(&self) -> f64 {
+        if self.runs == 0 {
+            return 0.0;
+        }
+        self.completed as f64 / self.runs as f64
+    }
+
+    /// Average moves taken by runs that reached an end room
+    pub fn average_moves(&self) -> Option<f64> {
+        if self.completed == 0 {
+            return None;
+        }
+        Some(self.moves_to_finish as f64 / self.completed as f64)
+    }
+}
+
+/// Plays `config.runs` games from `start` using `policy` and reports the results
+pub fn run_batch<P: Policy>(
+    start: &GameState,
+    policy: &mut P,
+    config: &SimulationConfig,
+) -> SimulationReport {
+    let mut rng = Rng::new(config.seed);
+    let mut reached: HashSet<String> = HashSet::new();
+    let mut report = SimulationReport {
+        runs: config.runs,
+        ..Default::default()
+    };
+
+    for _ in 0..config.runs {
+        let mut state = start.clone();
+        policy.reset();
+        reached.insert(state.play.current_room.clone());
+
+        let mut moves = 0;
+        let playing = |state: &GameState| !state.play.is_finished() && !state.play.is_game_over();
+        while playing(&state) && moves < config.max_moves {
+            // Policies only pick exits, so fights are always fought out
+            // and cutscenes skipped
+            if state.skip_cutscene() {
+                continue;
+            }
+            if state.attack() {
+                moves += 1;
+                continue;
+            }
+            let Some(index) = policy.choose(&state, &mut rng) else {
+                break;
+            };
+            state.choose_exit(index);
+            reached.insert(state.play.current_room.clone());
+            moves += 1;
+        }
+
+        if state.play.is_finished() {
+            report.completed += 1;
+            report.moves_to_finish += moves;
+        }
+    }
+
+    report.unreached_rooms = start
+        .maze
+        .rooms
+        .iter()
+        .filter(|room| !reached.contains(&room.id))
+        .map(|room| room.id.clone())
+        .collect();
+
+    report
+}
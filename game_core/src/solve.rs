@@ -0,0 +1,139 @@
+//! Finding the shortest way through a maze.
+//!
+//! Paths are lists of exit indices, the numbers
+//! [`GameState::choose_exit`] takes, so a path can be played back step by
+//! step or used as a hint.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::cooldown::ExitClosed;
+use crate::{GameState, Room};
+
+impl GameState {
+    /// Shortest path from the current room to the nearest end room.
+    /// Empty if the player is already at one; `None` if none is reachable.
+    pub fn solve(&self) -> Option<Vec<usize>> {
+        self.path_where(&self.play.current_room, |room| room.is_end)
+    }
+
+    /// The exit towards the nearest end room, counted as a hint used.
+    /// `None`, and not counted, if no end is reachable or the player is
+    /// at one.
+    pub fn use_hint(&mut self) -> Option<usize> {
+        let exit = self.solve()?.first().copied()?;
+        self.play.hints_used += 1;
+        Some(exit)
+    }
+
+    /// Shortest path from room `from` to room `to`, or `None` if `to`
+    /// cannot be reached
+    pub fn shortest_path(&self, from: &str, to: &str) -> Option<Vec<usize>> {
+        self.path_where(from, |room| room.id == to)
+    }
+
+    /// Breadth-first search from `from` to the nearest room passing `goal`,
+    /// over the exits open with the flags as they are now and every room
+    /// each may lead to
+    fn path_where(&self, from: &str, goal: impl Fn(&Room) -> bool) -> Option<Vec<usize>> {
+        // For each room found, the room and exit it was first reached by
+        let mut came_from: HashMap<&str, Option<(&str, usize)>> = HashMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(id) = queue.pop_front() {
+            let Some(room) = self.get_room(id) else {
+                continue;
+            };
+            if goal(room) {
+                let mut path = Vec::new();
+                let mut at = id;
+                while let Some(&Some((previous, exit))) = came_from.get(at) {
+                    path.push(exit);
+                    at = previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (index, exit) in room.exits.iter().enumerate() {
+                // Exits closed for a few turns open again; used-up ones never do
+                let used_up = self.exit_closed_in(room, index) == Some(ExitClosed::UsedUp);
+                if !exit.is_open(&self.play.flags) || used_up {
+                    continue;
+                }
+                // A random exit may lead to any of its outcomes
+                for destination in exit.destinations() {
+                    if !came_from.contains_key(destination) {
+                        came_from.insert(destination, Some((id, index)));
+                        queue.push_back(destination);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+
+    fn game(rooms: &str) -> GameState {
+        let file: MazeFile = serde_json::from_str(&format!(r#"{{"rooms": [{rooms}]}}"#)).unwrap();
+        GameState::from_maze(file).unwrap()
+    }
+
+    #[test]
+    fn follows_a_corridor() {
+        let state = game(
+            r#"{"id": "a", "description": "A", "exits": [
+                {"label": "Back", "destination": "a"},
+                {"label": "On", "destination": "b"}
+            ]},
+            {"id": "b", "description": "B", "exits": [
+                {"label": "On", "destination": "c"}
+            ]},
+            {"id": "c", "description": "C", "exits": [], "is_end": true}"#,
+        );
+        assert_eq!(state.shortest_path("a", "c"), Some(vec![1, 0]));
+        assert_eq!(state.shortest_path("b", "b"), Some(vec![]));
+    }
+
+    #[test]
+    fn none_when_the_room_cannot_be_reached() {
+        let state = game(
+            r#"{"id": "a", "description": "A", "exits": [
+                {"label": "On", "destination": "b"}
+            ]},
+            {"id": "b", "description": "B", "exits": [], "is_end": true},
+            {"id": "c", "description": "C", "exits": [
+                {"label": "Back", "destination": "a"}
+            ]}"#,
+        );
+        assert_eq!(state.shortest_path("a", "c"), None);
+        assert_eq!(state.shortest_path("c", "b"), Some(vec![0, 0]));
+    }
+
+    #[test]
+    fn avoids_closed_exits() {
+        let mut state = game(
+            r#"{"id": "a", "description": "A", "exits": [
+                {"label": "Locked door", "destination": "c", "when": "key"},
+                {"label": "Trapdoor", "destination": "c", "once": true},
+                {"label": "Long way", "destination": "b"}
+            ]},
+            {"id": "b", "description": "B", "exits": [
+                {"label": "On", "destination": "c"}
+            ]},
+            {"id": "c", "description": "C", "exits": [
+                {"label": "Back", "destination": "a"}
+            ], "is_end": true}"#,
+        );
+        assert_eq!(state.shortest_path("a", "c"), Some(vec![1]));
+
+        state.choose_exit(1);
+        assert_eq!(state.shortest_path("a", "c"), Some(vec![2, 0]));
+
+        state.set_flag("key", true);
+        assert_eq!(state.shortest_path("a", "c"), Some(vec![0]));
+    }
+}
@@ -0,0 +1,57 @@
+//! Short sounds for what happens in a game.
+//!
+//! A maze can name a sound for moving between rooms, picking an item up,
+//! winning and a few other moments. [`SoundEffects::for_event`] picks the
+//! one for each [`GameEvent`] the game reports, so frontends only need to
+//! play what the event queue asks for. Looping sounds by room come from
+//! [`ambience`](crate::ambience) and music from [`music`](crate::music).
+//!
+//! Core only picks the sounds; frontends play them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::event::GameEvent;
+
+/// Sound files for game events, relative to the maze file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SoundEffects {
+    /// Entering a room
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pick_up: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drop: Option<String>,
+    /// Reaching an ending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub win: Option<String>,
+    /// Losing the game
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lose: Option<String>,
+    /// Trying an exit that is closed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<String>,
+}
+
+impl SoundEffects {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The file to play for `event`, if the maze has one
+    pub fn for_event(&self, event: &GameEvent) -> Option<&str> {
+        let sound = match event {
+            GameEvent::RoomEntered { .. } => &self.step,
+            GameEvent::ItemTaken(_) => &self.pick_up,
+            GameEvent::ItemDropped(_) => &self.drop,
+            GameEvent::GameFinished { .. } => &self.win,
+            GameEvent::GameOver { .. } => &self.lose,
+            GameEvent::ExitBlocked(_) => &self.blocked,
+            GameEvent::BookmarkAdded(_)
+            | GameEvent::BookmarkRemoved(_)
+            | GameEvent::FreeRoamStarted
+            | GameEvent::EnemyDefeated(_) => return None,
+        };
+        sound.as_deref()
+    }
+}
@@ -0,0 +1,214 @@
+//! Where the player's files are kept: saves, ratings, ghosts and the
+//! rest.
+//!
+//! Everything the game keeps between runs goes through a [`Storage`], as
+//! text records named by a key such as `"autosave.json"`. The desktop
+//! app keeps them as files in a folder with [`FileStorage`]; tests, and
+//! frontends with nowhere to write files, can keep them in memory with
+//! [`MemoryStorage`]. Servers can keep them in an SQLite database with
+//! `SqliteStorage`, in builds with the `sqlite` feature. Whichever is
+//! used, the same code reads and writes them.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A place to keep text records by key.
+///
+/// Records are shared between threads, such as the app and its
+/// autosave, so implementations synchronise their own access.
+pub trait Storage: Send + Sync {
+    /// The record under `key`, or `None` if there is none yet
+    fn read(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error>>;
+
+    /// Replaces the record under `key` as a whole: readers see the old
+    /// record or the new one, never part of either
+    fn write(&self, key: &str, contents: &str) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Adds a line to the end of the record under `key`, starting it if
+    /// there is none
+    fn append_line(&self, key: &str, line: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut contents = self.read(key)?.unwrap_or_default();
+        contents.push_str(line);
+        contents.push('\n');
+        self.write(key, &contents)
+    }
+}
+
+/// Records as files in a folder, one per key.
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+/// Records held in memory, gone when it is dropped.
+#[derive(Default)]
+pub struct MemoryStorage {
+    records: Mutex<BTreeMap<String, String>>,
+}
+
+/// Records as rows of a table in an SQLite database, one per key.
+#[cfg(feature = "sqlite")]
+pub struct SqliteStorage {
+    connection: Mutex<rusqlite::Connection>,
+}
+
+impl FileStorage {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The file for `key`, which must name a file inside the folder
+    pub fn path(&self, key: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let relative = Path::new(key);
+        if key.is_empty()
+            || !relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(format!("{:?} is not a file name inside the storage folder", key).into());
+        }
+        Ok(self.dir.join(relative))
+    }
+}
+
+impl Storage for FileStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        match std::fs::read_to_string(self.path(key)?) {
+            Ok(text) => Ok(Some(text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes next to the file first and then renames it over, so a
+    /// crash mid-write never leaves a truncated file
+    fn write(&self, key: &str, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path = self.path(key)?;
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, contents)?;
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    fn append_line(&self, key: &str, line: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(key)?)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        Ok(self.records().get(key).cloned())
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.records().insert(key.to_string(), contents.to_string());
+        Ok(())
+    }
+}
+
+impl MemoryStorage {
+    /// A panic elsewhere leaves the records themselves whole, so they
+    /// are still used
+    fn records(&self) -> MutexGuard<'_, BTreeMap<String, String>> {
+        self.records.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteStorage {
+    /// Opens the database at `path`, creating it and its table if needed
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_connection(rusqlite::Connection::open(path)?)
+    }
+
+    /// A database held in memory, gone when it is dropped
+    pub fn open_in_memory() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_connection(rusqlite::Connection::open_in_memory()?)
+    }
+
+    fn with_connection(
+        connection: rusqlite::Connection,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS records (key TEXT PRIMARY KEY, contents TEXT NOT NULL)",
+        )?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// A panic elsewhere cannot leave a statement half done, so the
+    /// connection is still used
+    fn connection(&self) -> MutexGuard<'_, rusqlite::Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Each write and append is a single statement, so it is applied whole
+#[cfg(feature = "sqlite")]
+impl Storage for SqliteStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        use rusqlite::OptionalExtension;
+
+        let contents = self
+            .connection()
+            .query_row(
+                "SELECT contents FROM records WHERE key = ?1",
+                [key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(contents)
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.connection().execute(
+            "INSERT INTO records (key, contents) VALUES (?1, ?2) \
+             ON CONFLICT (key) DO UPDATE SET contents = excluded.contents",
+            [key, contents],
+        )?;
+        Ok(())
+    }
+
+    fn append_line(&self, key: &str, line: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.connection().execute(
+            "INSERT INTO records (key, contents) VALUES (?1, ?2) \
+             ON CONFLICT (key) DO UPDATE SET contents = contents || excluded.contents",
+            [key, &format!("{}\n", line)],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqlite_keeps_records_by_key() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        assert_eq!(storage.read("save.json").unwrap(), None);
+
+        storage.write("save.json", "first").unwrap();
+        storage.write("save.json", "second").unwrap();
+        assert_eq!(
+            storage.read("save.json").unwrap().as_deref(),
+            Some("second")
+        );
+
+        storage.append_line("ratings.jsonl", "1").unwrap();
+        storage.append_line("ratings.jsonl", "2").unwrap();
+        assert_eq!(
+            storage.read("ratings.jsonl").unwrap().as_deref(),
+            Some("1\n2\n")
+        );
+    }
+}
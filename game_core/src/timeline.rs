@@ -0,0 +1,79 @@
+//! Full play history for debugging, with the ability to scrub back and
+//! forth through it.
+//!
+//! Rather than a play state per step, the timeline keeps a keyframe
+//! every [`KEYFRAME_INTERVAL`] steps plus the actions in between, and
+//! rebuilds any step by replaying from the nearest keyframe. Actions are
+//! deterministic, so the rebuilt state is exactly the one played.
+
+use crate::action::Action;
+use crate::{GameState, PlayState};
+
+/// Steps between stored play states
+pub const KEYFRAME_INTERVAL: usize = 32;
+
+/// Every step of a game, from its start.
+pub struct Timeline {
+    /// Play state at steps 0, `KEYFRAME_INTERVAL`, 2 × `KEYFRAME_INTERVAL`, …
+    keyframes: Vec<PlayState>,
+    /// The action taken at each step
+    actions: Vec<Action>,
+    /// Step the game is currently at
+    position: usize,
+}
+
+impl Timeline {
+    /// Starts a timeline at the game's current state
+    pub fn new(state: &GameState) -> Self {
+        Self {
+            keyframes: vec![state.play.clone()],
+            actions: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Number of recorded steps
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Step the game is currently at
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Records an action that was just applied to `state`. After
+    /// rewinding, this drops the steps that came after.
+    pub fn record(&mut self, state: &GameState, action: Action) {
+        self.actions.truncate(self.position);
+        self.keyframes
+            .truncate(self.position / KEYFRAME_INTERVAL + 1);
+
+        self.actions.push(action);
+        self.position += 1;
+        if self.position.is_multiple_of(KEYFRAME_INTERVAL) {
+            self.keyframes.push(state.play.clone());
+        }
+    }
+
+    /// Puts `state` back to how it was after `step` steps, clearing its
+    /// undo history. Returns false if the timeline is shorter than that.
+    pub fn rewind_to(&mut self, state: &mut GameState, step: usize) -> bool {
+        if step > self.actions.len() {
+            return false;
+        }
+
+        let keyframe = step / KEYFRAME_INTERVAL;
+        state.play = self.keyframes[keyframe].clone();
+        for action in &self.actions[keyframe * KEYFRAME_INTERVAL..step] {
+            state.perform(action);
+        }
+        state.clear_undo();
+        self.position = step;
+        true
+    }
+}
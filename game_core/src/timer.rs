@@ -0,0 +1,42 @@
+//! Countdown for mazes with a time limit.
+//!
+//! Frontends report the time that passes with [`GameState::tick`]. The
+//! clock only runs while the player is exploring, not during cutscenes,
+//! and undo does not wind it back. When it runs out the game is lost.
+
+use std::time::Duration;
+
+use crate::event::GameEvent;
+use crate::{GameState, Phase};
+
+/// What the lose screen says ended a game that ran out of time
+pub const TIMEOUT_CAUSE: &str = "the clock";
+
+impl GameState {
+    /// Counts `elapsed` against the time limit, ending the game when
+    /// it runs out
+    pub fn tick(&mut self, elapsed: Duration) {
+        if self.play.phase != Phase::Exploring || self.in_cutscene() {
+            return;
+        }
+        let elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        self.play.elapsed_ms = self.play.elapsed_ms.saturating_add(elapsed_ms);
+        if self.time_left() == Some(Duration::ZERO) {
+            self.lose(TIMEOUT_CAUSE);
+            self.events.push(GameEvent::GameOver {
+                cause: TIMEOUT_CAUSE.to_string(),
+            });
+        }
+    }
+
+    /// Time played so far
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.play.elapsed_ms)
+    }
+
+    /// Time left before the game is lost, or `None` without a limit
+    pub fn time_left(&self) -> Option<Duration> {
+        let limit = Duration::from_secs(self.maze.time_limit_secs?);
+        Some(limit.saturating_sub(self.elapsed()))
+    }
+}
@@ -0,0 +1,155 @@
+//! Bookmarks and fast travel between visited rooms.
+//!
+//! Fast travel is only offered when the maze allows it or the player has
+//! finished, and only to rooms the player could walk to from where they
+//! stand, so it never skips past one-way passages.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use crate::GameState;
+
+/// Why a fast-travel request was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TravelError {
+    /// The maze does not allow fast travel and the player has not finished
+    NotAllowed,
+    /// The player has not been to this room
+    NotVisited(String),
+    /// No path leads from the current room to this one
+    Unreachable(String),
+    /// The player is in the middle of a fight
+    InCombat,
+    /// A cutscene is playing
+    InCutscene,
+}
+
+impl fmt::Display for TravelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotAllowed => write!(f, "fast travel is not allowed in this maze"),
+            Self::NotVisited(id) => write!(f, "room {:?} has not been visited", id),
+            Self::Unreachable(id) => write!(f, "room {:?} cannot be reached from here", id),
+            Self::InCombat => write!(f, "there is no running off in the middle of a fight"),
+            Self::InCutscene => write!(f, "wait for the cutscene to finish"),
+        }
+    }
+}
+
+impl std::error::Error for TravelError {}
+
+impl GameState {
+    /// Whether fast travel is open to the player right now
+    pub fn can_fast_travel(&self) -> bool {
+        (self.maze.allow_fast_travel || self.play.is_finished())
+            && !self.play.is_game_over()
+            && !self.in_combat()
+            && !self.in_cutscene()
+    }
+
+    /// Jumps straight to a visited room. Before finishing, the room must
+    /// be reachable on foot from the current one; after, travelling
+    /// starts free roam. Held back by what holds back
+    /// [`can_act`](GameState::can_act), except having finished.
+    pub fn travel_to_visited(&mut self, id: &str) -> Result<(), TravelError> {
+        if self.in_combat() {
+            return Err(TravelError::InCombat);
+        }
+        if self.in_cutscene() {
+            return Err(TravelError::InCutscene);
+        }
+        if !self.can_fast_travel() {
+            return Err(TravelError::NotAllowed);
+        }
+        if !self.has_visited(id) {
+            return Err(TravelError::NotVisited(id.to_string()));
+        }
+        if !self.play.is_finished() && !self.reachable_from_current(id) {
+            return Err(TravelError::Unreachable(id.to_string()));
+        }
+        // Moving on from an ending means exploring freely
+        self.start_free_roam();
+        self.move_to(id.to_string());
+        Ok(())
+    }
+
+    /// Adds or removes a bookmark on a visited room.
+    /// Returns whether the room is now bookmarked.
+    pub fn toggle_bookmark(&mut self, id: &str) -> bool {
+        if let Some(i) = self.play.bookmarks.iter().position(|b| b == id) {
+            self.play.bookmarks.remove(i);
+            false
+        } else if self.has_visited(id) {
+            self.play.bookmarks.push(id.to_string());
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_bookmarked(&self, id: &str) -> bool {
+        self.play.bookmarks.iter().any(|b| b == id)
+    }
+
+    /// Breadth-first search from the current room over exits that are
+    /// open and not closed after use, to any room they may lead to
+    fn reachable_from_current(&self, target: &str) -> bool {
+        let mut seen = HashSet::from([self.play.current_room.as_str()]);
+        let mut queue = VecDeque::from([self.play.current_room.as_str()]);
+        while let Some(id) = queue.pop_front() {
+            if id == target {
+                return true;
+            }
+            let Some(room) = self.get_room(id) else {
+                continue;
+            };
+            let exits = room.exits.iter().enumerate().filter(|(i, exit)| {
+                exit.is_open(&self.play.flags) && self.exit_closed_in(room, *i).is_none()
+            });
+            for (_, exit) in exits {
+                for destination in exit.destinations() {
+                    if seen.insert(destination) {
+                        queue.push_back(destination);
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+
+    #[test]
+    fn waits_for_cutscenes_even_after_finishing() {
+        let file: MazeFile = serde_json::from_str(
+            r#"{"allow_fast_travel": true, "rooms": [
+                {"id": "a", "description": "A", "exits": [
+                    {"label": "On", "destination": "b"}
+                ]},
+                {"id": "b", "description": "B", "exits": [
+                    {"label": "On", "destination": "end"}
+                ], "cutscene": {"beats": [{"text": "A door slams."}]}},
+                {"id": "end", "description": "End", "exits": [], "is_end": true,
+                 "cutscene": {"beats": [{"text": "Daylight."}]}}
+            ]}"#,
+        )
+        .unwrap();
+        let mut state = GameState::from_maze(file).unwrap();
+
+        state.choose_exit(0);
+        assert_eq!(state.travel_to_visited("a"), Err(TravelError::InCutscene));
+        assert!(state.skip_cutscene());
+        assert_eq!(state.travel_to_visited("b"), Ok(()));
+
+        state.choose_exit(0);
+        assert!(state.play.is_finished());
+        assert_eq!(state.travel_to_visited("a"), Err(TravelError::InCutscene));
+        assert!(state.skip_cutscene());
+        assert_eq!(state.travel_to_visited("a"), Ok(()));
+        assert!(state.is_roaming());
+    }
+}
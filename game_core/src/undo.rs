@@ -0,0 +1,100 @@
+//! Undo and redo of player actions.
+//!
+//! Each applied action stores the play state from before it, up to a
+//! configurable depth, so a wrong turn can be taken back without
+//! restarting. Undo history is not saved with the game.
+
+use std::collections::VecDeque;
+
+use crate::{GameState, PlayState};
+
+/// Actions kept for undo unless set otherwise
+pub const DEFAULT_UNDO_DEPTH: usize = 50;
+
+#[derive(Clone)]
+pub(crate) struct UndoHistory {
+    /// Oldest first
+    past: VecDeque<PlayState>,
+    /// Most recently undone last
+    future: Vec<PlayState>,
+    depth: usize,
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self {
+            past: VecDeque::new(),
+            future: Vec::new(),
+            depth: DEFAULT_UNDO_DEPTH,
+        }
+    }
+}
+
+impl UndoHistory {
+    /// Remembers the state from before an action, forgetting anything
+    /// that was undone
+    pub(crate) fn push(&mut self, before: PlayState) {
+        self.future.clear();
+        if self.depth == 0 {
+            return;
+        }
+        if self.past.len() == self.depth {
+            self.past.pop_front();
+        }
+        self.past.push_back(before);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.past.clear();
+        self.future.clear();
+    }
+}
+
+impl GameState {
+    /// Takes back the last action. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(before) = self.undo.past.pop_back() else {
+            return false;
+        };
+        let undone = std::mem::replace(&mut self.play, before);
+        // The clock keeps running through undo, and hints stay used
+        self.play.elapsed_ms = undone.elapsed_ms;
+        self.play.hints_used = undone.hints_used;
+        self.undo.future.push(undone);
+        true
+    }
+
+    /// Does the last undone action again. Returns false if there is
+    /// nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(after) = self.undo.future.pop() else {
+            return false;
+        };
+        let before = std::mem::replace(&mut self.play, after);
+        self.play.elapsed_ms = self.play.elapsed_ms.max(before.elapsed_ms);
+        self.play.hints_used = self.play.hints_used.max(before.hints_used);
+        self.undo.past.push_back(before);
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undo.future.is_empty()
+    }
+
+    /// Forgets every action that could be undone or redone
+    pub fn clear_undo(&mut self) {
+        self.undo.clear();
+    }
+
+    /// Sets how many actions can be undone; 0 turns undo off
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.undo.depth = depth;
+        while self.undo.past.len() > depth {
+            self.undo.past.pop_front();
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Structural checks for maze authors.
+//!
+//! Unlike [`GameState::from_rooms`](crate::GameState::from_rooms), which
+//! stops at the first problem that makes a maze unplayable, validation
+//! lists every problem, including ones a game can survive such as rooms
+//! nobody can reach.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::{MazeFile, Room};
+
+/// A structural problem found in a maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// The maze has no rooms
+    NoRooms,
+    /// More than one room has this id
+    DuplicateRoomId(String),
+    /// More than one item has this id
+    DuplicateItemId(String),
+    /// An exit leads to a room that does not exist
+    UnknownDestination {
+        room: String,
+        label: String,
+        destination: String,
+    },
+    /// A character's reply leads to a line they do not have
+    DanglingReply {
+        npc: String,
+        label: String,
+        line: String,
+    },
+    /// A character hands over an item the maze does not have
+    UnknownGift { npc: String, item: String },
+    /// No path leads from the start to this room
+    UnreachableRoom(String),
+    /// No end room can be reached from the start
+    NoReachableEnd,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoRooms => write!(f, "the maze has no rooms"),
+            Self::DuplicateRoomId(id) => write!(f, "more than one room has the id {:?}", id),
+            Self::DuplicateItemId(id) => write!(f, "more than one item has the id {:?}", id),
+            Self::UnknownDestination {
+                room,
+                label,
+                destination,
+            } => write!(
+                f,
+                "exit {:?} in room {:?} leads to unknown room {:?}",
+                label, room, destination
+            ),
+            Self::DanglingReply { npc, label, line } => write!(
+                f,
+                "reply {:?} to {:?} leads to unknown line {:?}",
+                label, npc, line
+            ),
+            Self::UnknownGift { npc, item } => {
+                write!(f, "{:?} gives unknown item {:?}", npc, item)
+            }
+            Self::UnreachableRoom(id) => {
+                write!(f, "room {:?} cannot be reached from the start", id)
+            }
+            Self::NoReachableEnd => write!(f, "no end room can be reached from the start"),
+        }
+    }
+}
+
+impl MazeFile {
+    /// Lists every structural problem in the maze; empty if there are none
+    pub fn validate(&self) -> Vec<Issue> {
+        let Some(start) = self.rooms.first() else {
+            return vec![Issue::NoRooms];
+        };
+        let mut issues = Vec::new();
+
+        let mut index = HashMap::new();
+        for (i, room) in self.rooms.iter().enumerate() {
+            if index.insert(room.id.as_str(), i).is_some() {
+                issues.push(Issue::DuplicateRoomId(room.id.clone()));
+            }
+        }
+
+        let mut items = HashSet::new();
+        for item in self.rooms.iter().flat_map(|room| &room.items) {
+            if !items.insert(item.id.as_str()) {
+                issues.push(Issue::DuplicateItemId(item.id.clone()));
+            }
+        }
+
+        for room in &self.rooms {
+            for exit in &room.exits {
+                for destination in exit.destinations() {
+                    if !index.contains_key(destination) {
+                        issues.push(Issue::UnknownDestination {
+                            room: room.id.clone(),
+                            label: exit.label.clone(),
+                            destination: destination.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        for npc in self.rooms.iter().flat_map(|room| &room.npcs) {
+            for reply in npc.dialogue.iter().flat_map(|line| &line.replies) {
+                if let Some(next) = &reply.next
+                    && npc.line(next).is_none()
+                {
+                    issues.push(Issue::DanglingReply {
+                        npc: npc.id.clone(),
+                        label: reply.label.clone(),
+                        line: next.clone(),
+                    });
+                }
+                for item in reply
+                    .give_items
+                    .iter()
+                    .filter(|id| !items.contains(id.as_str()))
+                {
+                    issues.push(Issue::UnknownGift {
+                        npc: npc.id.clone(),
+                        item: item.clone(),
+                    });
+                }
+            }
+        }
+
+        // Walk every exit from the start, to anywhere it may lead
+        let mut reached = HashSet::from([start.id.as_str()]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(room) = queue.pop_front() {
+            for destination in room.exits.iter().flat_map(|exit| exit.destinations()) {
+                if let Some(&i) = index.get(destination)
+                    && reached.insert(destination)
+                {
+                    queue.push_back(&self.rooms[i]);
+                }
+            }
+        }
+
+        let mut reported = HashSet::new();
+        for room in &self.rooms {
+            if !reached.contains(room.id.as_str()) && reported.insert(room.id.as_str()) {
+                issues.push(Issue::UnreachableRoom(room.id.clone()));
+            }
+        }
+        // Rooms naming an ending are ends too, as the game treats them
+        let is_end = |room: &&Room| room.is_end || room.ending.is_some();
+        if !self
+            .rooms
+            .iter()
+            .filter(is_end)
+            .any(|room| reached.contains(room.id.as_str()))
+        {
+            issues.push(Issue::NoReachableEnd);
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A start room that is also the end, holding a lamp and `npc`
+    fn maze(npc: &str) -> MazeFile {
+        serde_json::from_str(&format!(
+            r#"{{"rooms": [
+                {{"id": "hall", "description": "Hall", "exits": [], "is_end": true,
+                  "items": [{{"id": "lamp", "name": "a lamp"}}],
+                  "npcs": [{npc}]}}
+            ]}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn reports_replies_to_missing_lines() {
+        let file = maze(
+            r#"{"id": "guard", "name": "Guard", "dialogue": [
+                {"id": "hi", "text": "Halt!", "replies": [
+                    {"label": "Friend", "next": "pass"},
+                    {"label": "Again", "next": "hi"}
+                ]}
+            ]}"#,
+        );
+        assert_eq!(
+            file.validate(),
+            [Issue::DanglingReply {
+                npc: "guard".to_string(),
+                label: "Friend".to_string(),
+                line: "pass".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_gifts_of_missing_items() {
+        let file = maze(
+            r#"{"id": "guard", "name": "Guard", "dialogue": [
+                {"id": "hi", "text": "Take these.", "replies": [
+                    {"label": "Thanks", "give_items": ["lamp", "sword"]}
+                ]}
+            ]}"#,
+        );
+        assert_eq!(
+            file.validate(),
+            [Issue::UnknownGift {
+                npc: "guard".to_string(),
+                item: "sword".to_string(),
+            }]
+        );
+    }
+}
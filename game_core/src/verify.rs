@@ -0,0 +1,214 @@
+//! Checking submitted runs by playing them back, so a leaderboard only
+//! takes runs that really happened.
+//!
+//! A [`Submission`] carries the run's [`Replay`], the hash of the maze it
+//! was played on and what it claims. [`verify`] plays the replay on a
+//! fresh game of the maze and accepts the claim only if every action was
+//! allowed, every checkpoint matches and the game ended as claimed. It
+//! needs no frontend, so any server can run it on what it is sent.
+//!
+//! Replays hold actions rather than clock ticks, so time taken cannot be
+//! checked this way, only where the run went and how.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::replay::{Desync, Fnv, Replay};
+use crate::{GameState, Maze, Phase};
+
+/// A finished run sent in for a leaderboard.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Submission {
+    /// [`Maze::content_hash`] of the maze played
+    pub maze_hash: u64,
+    pub replay: Replay,
+    /// Id of the ending the run claims to reach
+    pub ending: String,
+    /// Steps the run claims to have taken
+    pub steps: u32,
+}
+
+/// Why a submission was turned down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The run was played on a different maze, or a changed one
+    WrongMaze {
+        expected: u64,
+        found: u64,
+    },
+    /// Playback went differently from the recording
+    Desync(Desync),
+    /// The replay stops before the game is over
+    NotFinished,
+    /// The game was lost rather than finished
+    Lost {
+        cause: String,
+    },
+    WrongEnding {
+        claimed: String,
+        found: String,
+    },
+    WrongSteps {
+        claimed: u32,
+        found: u32,
+    },
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongMaze { expected, found } => {
+                write!(f, "run is for maze {:016x}, not {:016x}", found, expected)
+            }
+            Self::Desync(desync) => write!(f, "replay does not play back: {}", desync),
+            Self::NotFinished => write!(f, "replay stops before the end"),
+            Self::Lost { cause } => write!(f, "run was lost to {}", cause),
+            Self::WrongEnding { claimed, found } => {
+                write!(f, "run claims ending {:?} but reaches {:?}", claimed, found)
+            }
+            Self::WrongSteps { claimed, found } => {
+                write!(f, "run claims {} steps but takes {}", claimed, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+impl Maze {
+    /// A hash of everything in the maze, stable across runs and
+    /// platforms, so a submission names exactly the maze it was played on
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = Fnv::new();
+        // Serialising always succeeds: the maze was read from the same form
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        hash.write(&bytes);
+        hash.0
+    }
+}
+
+/// Plays a submission back on a fresh game of `maze`. Returns the game
+/// as it ended, for working out the score, or why the run was turned down.
+pub fn verify(maze: &Maze, submission: &Submission) -> Result<GameState, Rejection> {
+    let expected = maze.content_hash();
+    if submission.maze_hash != expected {
+        return Err(Rejection::WrongMaze {
+            expected,
+            found: submission.maze_hash,
+        });
+    }
+
+    let mut state = GameState::start(maze.clone());
+    state.reseed(submission.replay.seed);
+    submission
+        .replay
+        .play(&mut state)
+        .map_err(Rejection::Desync)?;
+
+    if let Phase::GameOver { cause } = &state.play.phase {
+        return Err(Rejection::Lost {
+            cause: cause.clone(),
+        });
+    }
+    let ending = state.ending().ok_or(Rejection::NotFinished)?;
+    if ending.id != submission.ending {
+        return Err(Rejection::WrongEnding {
+            claimed: submission.ending.clone(),
+            found: ending.id,
+        });
+    }
+    if state.play.steps != submission.steps {
+        return Err(Rejection::WrongSteps {
+            claimed: submission.steps,
+            found: state.play.steps,
+        });
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MazeFile;
+    use crate::action::Action;
+
+    fn maze() -> Maze {
+        let file: MazeFile = serde_json::from_str(
+            r#"{"rooms": [
+                {"id": "hall", "description": "Hall", "exits": [
+                    {"label": "Left", "destination": "garden"},
+                    {"label": "Right", "destination": "cellar"}
+                ]},
+                {"id": "garden", "description": "Garden", "exits": [], "is_end": true},
+                {"id": "cellar", "description": "Cellar", "exits": [], "is_end": true}
+            ]}"#,
+        )
+        .unwrap();
+        Maze::new(file).unwrap()
+    }
+
+    /// A real run to the garden, as a frontend would record it
+    fn submission(maze: &Maze) -> Submission {
+        let mut state = GameState::start(maze.clone());
+        state.reseed(7);
+        let mut replay = Replay::new(&state);
+        state.apply(&Action::ChooseExit(0));
+        replay.record(&state, Action::ChooseExit(0));
+        Submission {
+            maze_hash: maze.content_hash(),
+            replay,
+            ending: "garden".to_string(),
+            steps: 1,
+        }
+    }
+
+    #[test]
+    fn accepts_a_run_that_happened() {
+        let maze = maze();
+        let state = verify(&maze, &submission(&maze)).unwrap();
+        assert_eq!(state.play.current_room, "garden");
+    }
+
+    #[test]
+    fn rejects_a_run_on_another_maze() {
+        let maze = maze();
+        let mut run = submission(&maze);
+        run.maze_hash ^= 1;
+        assert!(matches!(
+            verify(&maze, &run),
+            Err(Rejection::WrongMaze { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_a_changed_seed() {
+        let maze = maze();
+        let mut run = submission(&maze);
+        run.replay.seed += 1;
+        assert!(matches!(
+            verify(&maze, &run),
+            Err(Rejection::Desync(Desync::HashMismatch { step: 0, .. }))
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_actions() {
+        let maze = maze();
+        let mut run = submission(&maze);
+        run.replay.actions[0] = Action::ChooseExit(1);
+        assert_eq!(
+            verify(&maze, &run).err(),
+            Some(Rejection::WrongEnding {
+                claimed: "garden".to_string(),
+                found: "cellar".to_string(),
+            })
+        );
+
+        run.replay.actions[0] = Action::ChooseExit(2);
+        assert_eq!(
+            verify(&maze, &run).err(),
+            Some(Rejection::Desync(Desync::Rejected { step: 0 }))
+        );
+    }
+}
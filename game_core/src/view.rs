@@ -0,0 +1,220 @@
+//! What a frontend shows for the current moment of a game.
+//!
+//! [`GameState::view`] resolves everything a frontend needs to present:
+//! the description to print, every choice with whether it is available
+//! and why not, and progress figures. Frontends render from the view so
+//! they all show the same thing.
+
+use crate::action::Action;
+use crate::combat::Enemy;
+use crate::cutscene::Beat;
+use crate::npc::Npc;
+use crate::{GameState, Item, Phase, Room};
+
+/// The game as a frontend should present it.
+pub struct GameView<'a> {
+    pub room: &'a Room,
+    /// Room description, ready to print
+    pub description: String,
+    /// One entry per exit shown, in the room's order, disabled while
+    /// closed after use
+    pub exits: Vec<Choice>,
+    /// Items lying here, each offered for picking up
+    pub items: Vec<(&'a Item, Choice)>,
+    /// Carried items, each offered for putting down
+    pub inventory: Vec<(&'a Item, Choice)>,
+    /// Characters here, each offered to talk to
+    pub npcs: Vec<(&'a Npc, Choice)>,
+    /// The conversation going on, if any
+    pub conversation: Option<ConversationView<'a>>,
+    /// The fight going on, if any
+    pub combat: Option<CombatView<'a>>,
+    /// The cutscene playing, if any
+    pub cutscene: Option<CutsceneView<'a>>,
+    pub phase: &'a Phase,
+    pub stats: Stats,
+}
+
+/// A choice offered to the player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Choice {
+    pub label: String,
+    pub action: Action,
+    pub enabled: bool,
+    /// Why the choice is disabled
+    pub reason: Option<&'static str>,
+}
+
+/// A character's current line and the replies the player can give.
+pub struct ConversationView<'a> {
+    pub npc: &'a Npc,
+    pub text: &'a str,
+    /// The open replies, then one to walk away
+    pub replies: Vec<Choice>,
+}
+
+/// An enemy being fought and what the player can do about it.
+pub struct CombatView<'a> {
+    pub enemy: &'a Enemy,
+    pub enemy_hp: u32,
+    /// Attack, then flee
+    pub choices: Vec<Choice>,
+}
+
+/// A cutscene part way through.
+pub struct CutsceneView<'a> {
+    /// Beats shown so far, the current one last
+    pub shown: &'a [Beat],
+    pub beat: &'a Beat,
+    /// Continue, then skip
+    pub choices: Vec<Choice>,
+}
+
+/// Progress through the maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub rooms_visited: usize,
+    pub room_count: usize,
+    pub items_carried: usize,
+    /// Exits taken so far
+    pub steps: u32,
+    pub health: u32,
+    pub max_health: u32,
+    pub stamina: u32,
+    pub max_stamina: u32,
+}
+
+impl Choice {
+    fn new(label: String, action: Action, blocked: Option<&'static str>) -> Self {
+        Self {
+            label,
+            action,
+            enabled: blocked.is_none(),
+            reason: blocked,
+        }
+    }
+}
+
+impl GameState {
+    /// Resolves what to show for the current room
+    pub fn view(&self) -> GameView<'_> {
+        let room = self.current_room();
+        let phase = &self.play.phase;
+        let held_back = self.held_back();
+
+        GameView {
+            room,
+            description: self.room_description(room),
+            exits: self
+                .shown_exits()
+                .into_iter()
+                .map(|i| {
+                    let label = room.exits[i].label.clone();
+                    let closed = self.exit_closed(i).map(|_| "That way is closed for now");
+                    Choice::new(label, Action::ChooseExit(i), held_back.or(closed))
+                })
+                .collect(),
+            items: self
+                .items_here()
+                .into_iter()
+                .map(|item| {
+                    let take = Action::TakeItem(item.id.clone());
+                    (
+                        item,
+                        Choice::new(format!("Pick up {}", item.name), take, held_back),
+                    )
+                })
+                .collect(),
+            inventory: self
+                .inventory()
+                .into_iter()
+                .map(|item| {
+                    let drop = Action::DropItem(item.id.clone());
+                    (
+                        item,
+                        Choice::new(format!("Drop {}", item.name), drop, held_back),
+                    )
+                })
+                .collect(),
+            npcs: room
+                .npcs
+                .iter()
+                .map(|npc| {
+                    let talk = Action::Talk(npc.id.clone());
+                    (
+                        npc,
+                        Choice::new(format!("Talk to {}", npc.name), talk, held_back),
+                    )
+                })
+                .collect(),
+            conversation: self.conversation_view(),
+            combat: self.combat_view(),
+            cutscene: self.cutscene_view(),
+            phase,
+            stats: Stats {
+                rooms_visited: self.play.visited.len(),
+                room_count: self.maze.rooms.len(),
+                items_carried: self.play.inventory.len(),
+                steps: self.play.steps,
+                health: self.play.health,
+                max_health: self.maze.player.hp,
+                stamina: self.play.stamina,
+                max_stamina: self.maze.player.stamina,
+            },
+        }
+    }
+
+    fn conversation_view(&self) -> Option<ConversationView<'_>> {
+        let (npc, line) = self.conversation()?;
+        let mut replies: Vec<Choice> = self
+            .open_replies()
+            .into_iter()
+            .map(|i| Choice::new(line.replies[i].label.clone(), Action::Reply(i), None))
+            .collect();
+        let farewell = if line.replies.is_empty() {
+            "Goodbye"
+        } else {
+            "Walk away"
+        };
+        replies.push(Choice::new(
+            farewell.to_string(),
+            Action::EndConversation,
+            None,
+        ));
+        Some(ConversationView {
+            npc,
+            text: &line.text,
+            replies,
+        })
+    }
+
+    fn cutscene_view(&self) -> Option<CutsceneView<'_>> {
+        let (cutscene, beat) = self.cutscene()?;
+        let shown = cutscene.beats.get(..=beat)?;
+        let next = if beat + 1 < cutscene.beats.len() {
+            "Continue"
+        } else {
+            "Done"
+        };
+        Some(CutsceneView {
+            shown,
+            beat: &shown[beat],
+            choices: vec![
+                Choice::new(next.to_string(), Action::NextBeat, None),
+                Choice::new("Skip".to_string(), Action::SkipCutscene, None),
+            ],
+        })
+    }
+
+    fn combat_view(&self) -> Option<CombatView<'_>> {
+        let (enemy, enemy_hp) = self.combat()?;
+        Some(CombatView {
+            enemy,
+            enemy_hp,
+            choices: vec![
+                Choice::new(format!("Attack {}", enemy.name), Action::Attack, None),
+                Choice::new("Flee".to_string(), Action::Flee, None),
+            ],
+        })
+    }
+}
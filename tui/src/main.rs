@@ -0,0 +1,144 @@
+mod screen;
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use game_core::action::Action;
+use game_core::event::GameEvent;
+use game_core::locale::Locale;
+use game_core::{GameState, MazeFile, Phase};
+
+/// Played when no maze is given, if it is there
+const DEFAULT_MAZE: &str = "maze.json";
+
+const HELP: &str = "Type the number of a choice, or:
+  h  hint        u  undo        r  restart
+  f  free roam   ?  help        q  quit";
+
+fn main() -> ExitCode {
+    let mut path = None;
+    let mut seed = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => match args.next().as_deref().map(str::parse) {
+                Some(Ok(value)) => seed = Some(value),
+                _ => eprintln!("--seed expects a whole number"),
+            },
+            "--help" | "-h" => {
+                println!("Usage: tui [MAZE] [--seed N]\n\n{}", HELP);
+                return ExitCode::SUCCESS;
+            }
+            other if !other.starts_with('-') => path = Some(other.to_string()),
+            other => eprintln!("Ignoring unknown argument: {}", other),
+        }
+    }
+
+    let path = path.or_else(|| {
+        Path::new(DEFAULT_MAZE)
+            .exists()
+            .then(|| DEFAULT_MAZE.into())
+    });
+    let loaded = match &path {
+        Some(path) => MazeFile::load(path).and_then(GameState::from_maze),
+        None => Ok(GameState::new()),
+    };
+    let mut state = match loaded {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("Error loading {}: {}", path.unwrap_or_default(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    state.reseed(seed.unwrap_or_else(time_seed));
+
+    match play(
+        &mut state,
+        io::stdin().lock(),
+        io::stdout().lock(),
+        &Locale::from_env(),
+    ) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Show the game and carry out the player's commands until they quit or
+/// the input ends. Reads whole lines, so commands can be piped in.
+fn play(
+    state: &mut GameState,
+    input: impl BufRead,
+    mut out: impl Write,
+    locale: &Locale,
+) -> io::Result<()> {
+    writeln!(out, "{}", HELP)?;
+    let mut lines = input.lines();
+    let mut last_input = Instant::now();
+    loop {
+        for message in state.take_messages() {
+            writeln!(out, "{}", message)?;
+        }
+        let actions = screen::show(&mut out, state, locale)?;
+        write!(out, "> ")?;
+        out.flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            writeln!(out)?;
+            return Ok(());
+        };
+        // The clock runs while the player thinks, as in the desktop game
+        state.tick(last_input.elapsed());
+        last_input = Instant::now();
+
+        let command = line.trim();
+        match command {
+            "q" | "quit" => return Ok(()),
+            "?" | "help" => writeln!(out, "{}", HELP)?,
+            "r" | "restart" => state.restart(),
+            "u" | "undo" => {
+                if !state.undo() {
+                    writeln!(out, "Nothing to undo.")?;
+                }
+            }
+            "h" | "hint" => match state.use_hint() {
+                Some(index) => {
+                    let exit = &state.current_room().exits[index];
+                    writeln!(out, "Try \"{}\".", exit.label)?;
+                }
+                None => writeln!(out, "No hint here.")?,
+            },
+            "f" | "free" if matches!(state.play.phase, Phase::Finished { .. }) => {
+                state.apply(&Action::StartFreeRoam);
+            }
+            "" => {}
+            _ => match command
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| actions.get(n.checked_sub(1)?))
+            {
+                Some(action) => {
+                    state.apply(action);
+                }
+                None => writeln!(out, "Unknown command \"{}\". Type ? for help.", command)?,
+            },
+        }
+        for event in state.take_events() {
+            if let GameEvent::ExitBlocked(_) = event {
+                writeln!(out, "That way is closed.")?;
+            }
+        }
+    }
+}
+
+/// A seed that differs from one run to the next
+fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
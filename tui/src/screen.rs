@@ -0,0 +1,149 @@
+use std::io::{self, Write};
+
+use game_core::action::Action;
+use game_core::cooldown::ExitClosed;
+use game_core::locale::Locale;
+use game_core::view::{Choice, Stats};
+use game_core::{GameState, Phase};
+
+/// Print what the player sees now, numbering each choice they can make.
+/// Returns the actions in the order they were numbered, from 1.
+pub fn show(out: &mut impl Write, state: &GameState, locale: &Locale) -> io::Result<Vec<Action>> {
+    let view = state.view();
+    let mut actions = Vec::new();
+    writeln!(out)?;
+
+    if let Some(cutscene) = &view.cutscene {
+        writeln!(out, "{}", cutscene.beat.text)?;
+        list(out, &cutscene.choices, &mut actions)?;
+        return Ok(actions);
+    }
+    if let Some(conversation) = &view.conversation {
+        writeln!(out, "{}: \"{}\"", conversation.npc.name, conversation.text)?;
+        list(out, &conversation.replies, &mut actions)?;
+        return Ok(actions);
+    }
+    if let Some(combat) = &view.combat {
+        let hp = locale.number(combat.enemy_hp);
+        writeln!(out, "You are fighting {} ({} hp).", combat.enemy.name, hp)?;
+        health(out, &view.stats, locale)?;
+        list(out, &combat.choices, &mut actions)?;
+        return Ok(actions);
+    }
+
+    writeln!(out, "{}", view.description)?;
+    match view.phase {
+        Phase::Finished { .. } => {
+            ending(out, state, locale)?;
+            writeln!(
+                out,
+                "Type f to explore freely, r to play again or q to quit."
+            )?;
+            return Ok(actions);
+        }
+        Phase::GameOver { cause } => {
+            writeln!(out, "You were lost to {}.", cause)?;
+            writeln!(out, "Type r to try again or q to quit.")?;
+            return Ok(actions);
+        }
+        Phase::Exploring | Phase::FreeRoam { .. } => {}
+    }
+
+    if let Some(left) = state.time_left() {
+        writeln!(out, "Time left: {}", locale.duration(left))?;
+    }
+    if view.stats.max_health > 0 && view.stats.health < view.stats.max_health {
+        health(out, &view.stats, locale)?;
+    }
+    for (npc, _) in &view.npcs {
+        writeln!(out, "{} is here.", npc.name)?;
+    }
+    if !view.inventory.is_empty() {
+        let names: Vec<_> = view
+            .inventory
+            .iter()
+            .map(|(item, _)| item.name.as_str())
+            .collect();
+        writeln!(out, "You carry {}.", names.join(", "))?;
+    }
+
+    if !view.exits.is_empty() {
+        writeln!(out, "Exits:")?;
+    }
+    for exit in &view.exits {
+        let closed = match exit.action {
+            Action::ChooseExit(index) => state.exit_closed(index),
+            _ => None,
+        };
+        match closed {
+            Some(ExitClosed::Turns(turns)) => {
+                let turns = locale.number(turns);
+                writeln!(out, "  -  {} (closed for {} more turns)", exit.label, turns)?;
+            }
+            Some(ExitClosed::UsedUp) => writeln!(out, "  -  {} (used up)", exit.label)?,
+            None => choice(out, exit, &mut actions)?,
+        }
+    }
+    list(
+        out,
+        view.items.iter().map(|(_, choice)| choice),
+        &mut actions,
+    )?;
+    list(
+        out,
+        view.npcs.iter().map(|(_, choice)| choice),
+        &mut actions,
+    )?;
+    list(
+        out,
+        view.inventory.iter().map(|(_, choice)| choice),
+        &mut actions,
+    )?;
+    Ok(actions)
+}
+
+/// The ending reached and how it scored
+fn ending(out: &mut impl Write, state: &GameState, locale: &Locale) -> io::Result<()> {
+    if let Some(ending) = state.ending() {
+        writeln!(out, "*** {} ***", ending.title)?;
+        if let Some(epilogue) = &ending.epilogue {
+            writeln!(out, "{}", epilogue)?;
+        }
+    }
+    if let Some(score) = state.score() {
+        writeln!(
+            out,
+            "Score: {} ({} steps, par {}, {} hints)",
+            locale.number(score.points),
+            locale.number(score.steps),
+            locale.number(score.par),
+            locale.number(score.hints),
+        )?;
+    }
+    Ok(())
+}
+
+fn health(out: &mut impl Write, stats: &Stats, locale: &Locale) -> io::Result<()> {
+    let (health, max) = (locale.number(stats.health), locale.number(stats.max_health));
+    writeln!(out, "Health: {}/{}", health, max)
+}
+
+fn list<'a>(
+    out: &mut impl Write,
+    choices: impl IntoIterator<Item = &'a Choice>,
+    actions: &mut Vec<Action>,
+) -> io::Result<()> {
+    choices
+        .into_iter()
+        .try_for_each(|c| choice(out, c, actions))
+}
+
+/// One choice, numbered if it can be made and with the reason if not
+fn choice(out: &mut impl Write, choice: &Choice, actions: &mut Vec<Action>) -> io::Result<()> {
+    if !choice.enabled {
+        let reason = choice.reason.unwrap_or("not now");
+        return writeln!(out, "  -  {} ({})", choice.label, reason);
+    }
+    actions.push(choice.action.clone());
+    writeln!(out, "  {:>2}. {}", actions.len(), choice.label)
+}